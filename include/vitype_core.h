@@ -2,6 +2,7 @@
 #define VITYPE_CORE_H
 
 #include <stdbool.h>
+#include <stddef.h>
 #include <stdint.h>
 
 #ifdef __cplusplus
@@ -12,22 +13,69 @@ typedef struct VitypeEngine VitypeEngine;
 
 typedef struct {
     bool has_action;
-    int32_t delete_count;
+    int32_t delete_count; // Output text to delete, in the unit set with vitype_engine_set_delete_count_unit
     char *text;
 } VitypeTransformResult;
 
+typedef struct {
+    uint8_t *data;
+    size_t len;
+} VitypeBytes;
+
 VitypeEngine *vitype_engine_new(void);
 void vitype_engine_free(VitypeEngine *engine);
 void vitype_engine_reset(VitypeEngine *engine);
 void vitype_engine_delete_last_character(VitypeEngine *engine);
 void vitype_engine_set_auto_fix_tone(VitypeEngine *engine, bool enabled);
 void vitype_engine_set_free_tone_placement(VitypeEngine *engine, bool enabled);
-void vitype_engine_set_input_method(VitypeEngine *engine, int32_t method);  // 0 = Telex, 1 = VNI
+void vitype_engine_set_literal_foreign_initials(VitypeEngine *engine, bool enabled);
+void vitype_engine_set_telex_bracket_shortcuts(VitypeEngine *engine, bool enabled);
+void vitype_engine_set_quick_telex(VitypeEngine *engine, bool enabled);
+void vitype_engine_set_quick_consonants(VitypeEngine *engine, bool enabled);
+void vitype_engine_set_check_spelling(VitypeEngine *engine, bool enabled);
+void vitype_engine_set_restore_invalid_words(VitypeEngine *engine, bool enabled);
+void vitype_engine_set_english_bypass(VitypeEngine *engine, bool enabled);
+void vitype_engine_set_input_method(VitypeEngine *engine, int32_t method);  // 0 = Telex, 1 = VNI, 2 = VIQR, 3 = Simple Telex, 4 = Microsoft Vietnamese
+// 0 = Unicode, 1 = Composite Unicode, 2 = TCVN3, 3 = VISCII, 4 = VNI-Windows,
+// 5 = VIQR, 6 = HTML entities, 7 = \u escapes, 8 = Windows-1258
+// vitype_engine_process still returns UTF-8 text, unlike vitype_convert_text. For the code pages
+// (TCVN3, VISCII, VNI-Windows, Windows-1258) each code-page byte 0xNN arrives as the character
+// U+00NN, UTF-8 encoded: decode the text and narrow each code point to a byte to get the raw
+// bytes. Characters the code page has no byte for are written as '?'.
 void vitype_engine_set_output_encoding(VitypeEngine *engine, int32_t encoding);
+// 0 = Code points, 1 = UTF-16 code units, 2 = Bytes (UTF-8, one per character in code pages), 3 = Grapheme clusters
+void vitype_engine_set_delete_count_unit(VitypeEngine *engine, int32_t unit);
 void vitype_engine_set_tone_placement(VitypeEngine *engine, int32_t placement); // 0 = Orthographic, 1 = NucleusOnly
+// Settings files (TOML, or JSON when the path ends in ".json"). Return values:
+// 0 = OK, -1 = invalid argument, 1 = I/O error, 2 = parse error,
+// 3 = unsupported settings version, 4 = invalid option value.
+int32_t vitype_engine_load_settings(VitypeEngine *engine, const char *path_utf8);
+int32_t vitype_engine_save_settings(const VitypeEngine *engine, const char *path_utf8);
+// Loads a keymap file (same status codes) and makes it the active input method.
+int32_t vitype_engine_load_keymap(VitypeEngine *engine, const char *path_utf8);
+// Adds a word list file (one word per line, # comments) to the English bypass list.
+int32_t vitype_engine_load_english_words(VitypeEngine *engine, const char *path_utf8);
+// Exclusion list: words that are never transformed. kind: 0 = exact word, 1 = prefix.
+// add/remove return whether the list changed; load/save use the settings status codes.
+bool vitype_engine_add_exclusion(VitypeEngine *engine, const char *pattern_utf8, int32_t kind, bool case_sensitive);
+bool vitype_engine_remove_exclusion(VitypeEngine *engine, const char *pattern_utf8, int32_t kind, bool case_sensitive);
+void vitype_engine_clear_exclusions(VitypeEngine *engine);
+int32_t vitype_engine_load_exclusions(VitypeEngine *engine, const char *path_utf8);
+int32_t vitype_engine_save_exclusions(const VitypeEngine *engine, const char *path_utf8);
 VitypeTransformResult vitype_engine_process(VitypeEngine *engine, const char *input_utf8);
 void vitype_engine_free_string(char *text);
 
+// Whole-text conversion. Encodings use the vitype_engine_set_output_encoding numbering; Unicode,
+// VIQR, HTML entities and \u escapes are UTF-8, the code pages one byte per character.
+VitypeBytes vitype_convert_text(const uint8_t *input, size_t len, int32_t from, int32_t to);
+int32_t vitype_detect_encoding(const uint8_t *input, size_t len);
+void vitype_bytes_free(VitypeBytes bytes);
+
+// Diacritic stripping. Return null for invalid UTF-8; free with vitype_engine_free_string.
+char *vitype_remove_tones(const char *input_utf8);
+char *vitype_remove_diacritics(const char *input_utf8);
+char *vitype_slugify(const char *input_utf8);
+
 #ifdef __cplusplus
 }
 #endif
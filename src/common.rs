@@ -1,33 +1,95 @@
 use once_cell::sync::Lazy;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::keymap::Keymap;
 
 // ==================== Enums ====================
 
+/// Text form of the replacement text returned by [`crate::VitypeEngine::process_encoded`].
+///
+/// [`crate::VitypeEngine::process`] always returns precomposed Unicode.
 #[repr(C)]
-#[derive(Clone, Copy, PartialEq, Default)]
-pub(crate) enum OutputEncoding {
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum OutputEncoding {
+    /// Precomposed Unicode (NFC), e.g. `ệ` is a single code point.
     #[default]
     Unicode = 0,
+    /// Decomposed Unicode (NFD), e.g. `ệ` is `e` + U+0323 + U+0302.
     CompositeUnicode = 1,
+    /// TCVN3 (ABC) single-byte code page, one `char` per byte (U+0000..=U+00FF).
+    Tcvn3 = 2,
+    /// VISCII single-byte code page, one `char` per byte (U+0000..=U+00FF).
+    Viscii = 3,
+    /// VNI-Windows font encoding, one `char` per byte (U+0000..=U+00FF). Most letters take two
+    /// bytes, a base letter and a diacritic.
+    VniWindows = 4,
+    /// VIQR in plain ASCII, e.g. `ệ` is `e^.`.
+    Viqr = 5,
+    /// HTML numeric character references for non-ASCII characters, e.g. `ệ` is `&#7879;`.
+    HtmlEntity = 6,
+    /// `\u` escapes of UTF-16 code units for non-ASCII characters, e.g. `ệ` is `\u1EC7`.
+    UnicodeEscape = 7,
+    /// Windows-1258, one `char` per byte (U+0000..=U+00FF). Shaped letters are single bytes and
+    /// tones are separate combining-mark bytes, e.g. `ệ` is `ê` + dot below.
+    Cp1258 = 8,
 }
 
+/// Unit `delete_count` is measured in by [`crate::VitypeEngine::process_encoded`], i.e. what one
+/// backspace removes in the host.
 #[repr(C)]
-#[derive(Clone, Copy, PartialEq, Default)]
-pub(crate) enum InputMethod {
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DeleteCountUnit {
+    /// Unicode scalar values (Rust `char`s).
+    #[default]
+    CodePoints = 0,
+    /// UTF-16 code units, as counted by Cocoa and Win32.
+    Utf16CodeUnits = 1,
+    /// Bytes, as counted by byte-oriented terminals: UTF-8 bytes, or one byte per character in
+    /// the code pages (TCVN3, VISCII, VNI-Windows, Windows-1258).
+    Utf8Bytes = 2,
+    /// Extended grapheme clusters (Unicode UAX #29). In the code pages every byte is a character
+    /// of its own, so a VNI-Windows or Windows-1258 diacritic byte is a cluster too.
+    GraphemeClusters = 3,
+}
+
+/// Keymap used to interpret keystrokes.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum InputMethod {
+    /// Letter-based keys (`aa` → â, `w` → ư, `s`/`f`/`r`/`x`/`j` tones). See `TELEX_RULES.md`.
     #[default]
-    Telex = 0,
-    Vni = 1,
+    Telex,
+    /// Digit-based keys (`6`/`7`/`8` shapes, `9` → đ, `1`-`5` tones). See `VNI_RULES.md`.
+    Vni,
+    /// Punctuation-based keys (`^`/`+`/`(` shapes, `dd`/`d-` → đ, `'` `` ` `` `?` `~` `.` tones,
+    /// `\` escape). See `VIQR_RULES.md`.
+    Viqr,
+    /// Telex where `w` only shapes a preceding a/o/u and is otherwise a literal `w`.
+    SimpleTelex,
+    /// Windows "Vietnamese" keyboard layout: ă â ê ô ơ ư đ arrive as typed characters and tone
+    /// keys send combining marks (U+0300, U+0301, U+0303, U+0309, U+0323).
+    /// See `MS_VIETNAMESE_RULES.md`.
+    MicrosoftVietnamese,
+    /// User-defined keymap, usually loaded with [`Keymap::load`]. See `CUSTOM_KEYMAPS.md`.
+    Custom(Arc<Keymap>),
 }
 
+/// Rule used to choose which vowel of a cluster carries the tone mark.
 #[repr(C)]
-#[derive(Clone, Copy, PartialEq, Default)]
-pub(crate) enum TonePlacement {
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum TonePlacement {
+    /// Traditional orthography: `hòa`, `thủy`.
     #[default]
     Orthographic = 0,
+    /// Always mark the phonological nucleus: `hoà`, `thuỷ`.
     NucleusOnly = 1,
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub(crate) enum WTransformKind {
     None,
     Standalone,
@@ -39,10 +101,15 @@ pub(crate) enum WTransformKind {
 
 // ==================== Action Struct ====================
 
-#[derive(Clone, Debug, PartialEq)]
-pub(crate) struct KeyTransformAction {
-    pub(crate) delete_count: usize,
-    pub(crate) text: String,
+/// Edit the host must apply to its text in place of inserting the typed key.
+///
+/// Delete `delete_count` characters before the cursor, then insert `text`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyTransformAction {
+    /// Number of already-emitted characters to remove (backspaces).
+    pub delete_count: usize,
+    /// Replacement text to insert after deleting.
+    pub text: String,
 }
 
 // ==================== Helper Functions ====================
@@ -0,0 +1,140 @@
+use crate::common::{DeleteCountUnit, InputMethod, OutputEncoding, TonePlacement};
+
+// ==================== EngineConfig ====================
+
+/// Every user-facing option of [`crate::VitypeEngine`], as one value.
+///
+/// Build one with [`EngineConfig::builder`] (or start from [`Default`]) and hand it to
+/// [`crate::VitypeEngine::with_config`] or [`crate::VitypeEngine::apply_config`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EngineConfig {
+    pub input_method: InputMethod,
+    pub output_encoding: OutputEncoding,
+    /// What one backspace removes in the host; the unit of encoded `delete_count`.
+    pub delete_count_unit: DeleteCountUnit,
+    pub tone_placement: TonePlacement,
+    /// Move an existing tone to the correct vowel as more vowels are typed.
+    pub auto_fix_tone: bool,
+    /// Allow tones on vowel clusters that are not valid Vietnamese.
+    pub free_tone_placement: bool,
+    /// Type words starting with `f`, `j`, `z` (and `w` in Simple Telex) literally.
+    pub literal_foreign_initials: bool,
+    /// Type ơ/ư/Ơ/Ư with `[` `]` `{` `}` in Telex.
+    pub telex_bracket_shortcuts: bool,
+    /// Expand doubled initial consonants in Telex (`cc` → ch, `nn` → ng, ...).
+    pub quick_telex: bool,
+    /// Telex start (`f`/`j`/`w` → ph/gi/qu) and end (`g`/`h`/`k` → ng/nh/ch) consonant shortcuts.
+    pub quick_consonants: bool,
+    /// Refuse tones that leave the word misspelled (`càp`, `ngí`).
+    pub check_spelling: bool,
+    /// At the word boundary, replace a word that is not a valid syllable with its raw keys.
+    pub restore_invalid_words: bool,
+    /// Type English words (`mass`, `week`) as-is; see [`crate::VitypeEngine::set_english_bypass`].
+    pub english_bypass: bool,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            input_method: InputMethod::Telex,
+            output_encoding: OutputEncoding::Unicode,
+            delete_count_unit: DeleteCountUnit::CodePoints,
+            tone_placement: TonePlacement::Orthographic,
+            auto_fix_tone: true,
+            free_tone_placement: false,
+            literal_foreign_initials: false,
+            telex_bracket_shortcuts: false,
+            quick_telex: false,
+            quick_consonants: false,
+            check_spelling: false,
+            restore_invalid_words: false,
+            english_bypass: false,
+        }
+    }
+}
+
+impl EngineConfig {
+    pub fn builder() -> EngineConfigBuilder {
+        EngineConfigBuilder::default()
+    }
+}
+
+// ==================== EngineConfigBuilder ====================
+
+/// Builder for [`EngineConfig`]. Options that are not set keep their default value.
+#[derive(Clone, Debug, Default)]
+pub struct EngineConfigBuilder {
+    config: EngineConfig,
+}
+
+impl EngineConfigBuilder {
+    pub fn input_method(mut self, method: InputMethod) -> Self {
+        self.config.input_method = method;
+        self
+    }
+
+    pub fn output_encoding(mut self, encoding: OutputEncoding) -> Self {
+        self.config.output_encoding = encoding;
+        self
+    }
+
+    pub fn delete_count_unit(mut self, unit: DeleteCountUnit) -> Self {
+        self.config.delete_count_unit = unit;
+        self
+    }
+
+    pub fn tone_placement(mut self, placement: TonePlacement) -> Self {
+        self.config.tone_placement = placement;
+        self
+    }
+
+    pub fn auto_fix_tone(mut self, enabled: bool) -> Self {
+        self.config.auto_fix_tone = enabled;
+        self
+    }
+
+    pub fn free_tone_placement(mut self, enabled: bool) -> Self {
+        self.config.free_tone_placement = enabled;
+        self
+    }
+
+    pub fn literal_foreign_initials(mut self, enabled: bool) -> Self {
+        self.config.literal_foreign_initials = enabled;
+        self
+    }
+
+    pub fn telex_bracket_shortcuts(mut self, enabled: bool) -> Self {
+        self.config.telex_bracket_shortcuts = enabled;
+        self
+    }
+
+    pub fn quick_telex(mut self, enabled: bool) -> Self {
+        self.config.quick_telex = enabled;
+        self
+    }
+
+    pub fn quick_consonants(mut self, enabled: bool) -> Self {
+        self.config.quick_consonants = enabled;
+        self
+    }
+
+    pub fn check_spelling(mut self, enabled: bool) -> Self {
+        self.config.check_spelling = enabled;
+        self
+    }
+
+    pub fn restore_invalid_words(mut self, enabled: bool) -> Self {
+        self.config.restore_invalid_words = enabled;
+        self
+    }
+
+    pub fn english_bypass(mut self, enabled: bool) -> Self {
+        self.config.english_bypass = enabled;
+        self
+    }
+
+    pub fn build(self) -> EngineConfig {
+        self.config
+    }
+}
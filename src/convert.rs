@@ -0,0 +1,363 @@
+//! Whole-text conversion between the encodings of [`OutputEncoding`].
+//!
+//! Text is handled as bytes: the Unicode forms and the ASCII forms (VIQR, HTML entities, `\u`
+//! escapes) as UTF-8, the legacy code pages one byte per character. Everything goes through
+//! precomposed Unicode, using the same letter tables the engine emits with, so converting to an
+//! encoding and back returns the original text except where the target cannot represent it
+//! (uppercase toned letters in TCVN3, characters outside a code page).
+
+use std::collections::HashMap;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::common::{is_vowel, OutputEncoding, TONED_TO_BASE, VOWEL_TO_TONED};
+use crate::diacritics::{apply_shape, apply_tone, VowelShape};
+use crate::encoding::encode_char;
+use crate::keymap::{internal_tone_key, shape_for_action};
+use crate::viqr::{VIQR_KEYMAP, VIQR_LITERAL_PREFIX};
+use crate::Syllable;
+
+/// Converts `input` from one encoding to another.
+pub fn convert_text(input: &[u8], from: OutputEncoding, to: OutputEncoding) -> Vec<u8> {
+    encode_text(&decode_text(input, from), to)
+}
+
+/// Decodes `bytes` in `encoding` to precomposed Unicode.
+pub fn decode_text(bytes: &[u8], encoding: OutputEncoding) -> String {
+    let decoded = match encoding {
+        OutputEncoding::Unicode | OutputEncoding::CompositeUnicode => {
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+        OutputEncoding::Viqr => decode_viqr(&String::from_utf8_lossy(bytes)),
+        OutputEncoding::HtmlEntity => decode_html_entities(&String::from_utf8_lossy(bytes)),
+        OutputEncoding::UnicodeEscape => decode_unicode_escapes(&String::from_utf8_lossy(bytes)),
+        OutputEncoding::Tcvn3
+        | OutputEncoding::Viscii
+        | OutputEncoding::VniWindows
+        | OutputEncoding::Cp1258 => decode_code_page(bytes, encoding),
+    };
+    decoded.nfc().collect()
+}
+
+/// Encodes Unicode `text` (in any normalization form) as bytes in `encoding`.
+///
+/// Characters a code page has no byte for become `?`.
+pub fn encode_text(text: &str, encoding: OutputEncoding) -> Vec<u8> {
+    let text: String = text.nfc().collect();
+    match encoding {
+        OutputEncoding::Unicode => text.into_bytes(),
+        OutputEncoding::CompositeUnicode => text.nfd().collect::<String>().into_bytes(),
+        OutputEncoding::Viqr => encode_viqr(&text).into_bytes(),
+        OutputEncoding::HtmlEntity => encode_escaped(&text, '&', "&amp;", encoding).into_bytes(),
+        OutputEncoding::UnicodeEscape => encode_escaped(&text, '\\', "\\\\", encoding).into_bytes(),
+        OutputEncoding::Tcvn3
+        | OutputEncoding::Viscii
+        | OutputEncoding::VniWindows
+        | OutputEncoding::Cp1258 => text
+            .chars()
+            .flat_map(|ch| encode_char(ch, encoding).chars().collect::<Vec<_>>())
+            .map(|ch| u8::try_from(ch).unwrap_or(b'?'))
+            .collect(),
+    }
+}
+
+/// Guesses the encoding of `bytes`.
+///
+/// Valid UTF-8 with non-ASCII text is Unicode (decomposed if it holds combining marks). Plain
+/// ASCII is checked for HTML entities, `\u` escapes and VIQR, and is Unicode if none show up.
+/// Anything else is decoded with each legacy code page, and the one that yields the most
+/// plausible Vietnamese words wins.
+pub fn detect_encoding(bytes: &[u8]) -> OutputEncoding {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        if !text.is_ascii() {
+            return if text.chars().any(is_combining_mark) {
+                OutputEncoding::CompositeUnicode
+            } else {
+                OutputEncoding::Unicode
+            };
+        }
+        return [
+            OutputEncoding::HtmlEntity,
+            OutputEncoding::UnicodeEscape,
+            OutputEncoding::Viqr,
+        ]
+        .into_iter()
+        .map(|encoding| (encoding, plausibility(&decode_text(bytes, encoding))))
+        .filter(|&(_, score)| score > 0)
+        .max_by_key(|&(_, score)| score)
+        .map_or(OutputEncoding::Unicode, |(encoding, _)| encoding);
+    }
+
+    // `max_by_key` keeps the last maximum, so list the more common encodings last.
+    [
+        OutputEncoding::Cp1258,
+        OutputEncoding::Viscii,
+        OutputEncoding::VniWindows,
+        OutputEncoding::Tcvn3,
+    ]
+    .into_iter()
+    .max_by_key(|&encoding| plausibility(&decode_text(bytes, encoding)))
+    .unwrap_or(OutputEncoding::Tcvn3)
+}
+
+// ==================== Code Pages ====================
+
+/// Every precomposed Vietnamese letter, lowercase first.
+fn vietnamese_letters() -> Vec<char> {
+    let mut letters: Vec<char> = VOWEL_TO_TONED
+        .iter()
+        .flat_map(|(base, tones)| std::iter::once(*base).chain(tones.values().copied()))
+        .chain(['đ', 'Đ'])
+        .collect();
+    letters.sort_by_key(|&ch| (ch.is_uppercase(), ch));
+    letters
+}
+
+fn decode_code_page(bytes: &[u8], encoding: OutputEncoding) -> String {
+    // Where a code page shares one form between letters (TCVN3 has no uppercase toned letters),
+    // the lowercase letter wins.
+    let mut table: HashMap<String, char> = HashMap::new();
+    for letter in vietnamese_letters() {
+        table.entry(encode_char(letter, encoding)).or_insert(letter);
+    }
+    let longest = table
+        .keys()
+        .map(|key| key.chars().count())
+        .max()
+        .unwrap_or(1);
+
+    let chars: Vec<char> = bytes.iter().map(|&byte| char::from(byte)).collect();
+    let mut output = String::with_capacity(chars.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let matched = (1..=longest.min(chars.len() - index))
+            .rev()
+            .find_map(|len| {
+                let key: String = chars[index..index + len].iter().collect();
+                table.get(&key).map(|&letter| (letter, len))
+            });
+        match matched {
+            Some((letter, len)) => {
+                output.push(letter);
+                index += len;
+            }
+            None => {
+                output.push(chars[index]);
+                index += 1;
+            }
+        }
+    }
+    output
+}
+
+// ==================== VIQR ====================
+
+fn viqr_shape_for_key(key: char) -> Option<VowelShape> {
+    shape_for_action(VIQR_KEYMAP.action_for(key)?)
+}
+
+fn viqr_tone_key_to_internal(key: char) -> Option<char> {
+    internal_tone_key(VIQR_KEYMAP.action_for(key)?)
+}
+
+fn is_viqr_mark(ch: char) -> bool {
+    viqr_shape_for_key(ch).is_some() || viqr_tone_key_to_internal(ch).is_some()
+}
+
+/// Like the engine's VIQR output, plus a `\` before any character that would otherwise be read
+/// as part of the preceding letter.
+fn encode_viqr(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut previous: Option<char> = None;
+    for ch in text.chars() {
+        let ambiguous = match ch {
+            VIQR_LITERAL_PREFIX => true,
+            'd' | 'D' => matches!(previous, Some('d' | 'D')),
+            _ => is_viqr_mark(ch) && previous.is_some_and(is_vowel),
+        };
+        if ambiguous {
+            output.push(VIQR_LITERAL_PREFIX);
+        }
+        output.push_str(&encode_char(ch, OutputEncoding::Viqr));
+        previous = Some(ch);
+    }
+    output
+}
+
+fn decode_viqr(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        index += 1;
+
+        if ch == VIQR_LITERAL_PREFIX && index < chars.len() {
+            output.push(chars[index]);
+            index += 1;
+            continue;
+        }
+
+        if matches!(ch, 'd' | 'D') && matches!(chars.get(index), Some('d' | 'D')) {
+            output.push(if ch == 'D' { 'Đ' } else { 'đ' });
+            index += 1;
+            continue;
+        }
+
+        if !ch.is_ascii() || !is_vowel(ch) {
+            output.push(ch);
+            continue;
+        }
+
+        let mut letter = ch;
+        if let Some(shaped) = chars
+            .get(index)
+            .and_then(|&key| viqr_shape_for_key(key).and_then(|shape| apply_shape(letter, shape)))
+        {
+            letter = shaped;
+            index += 1;
+        }
+        if let Some(toned) = chars.get(index).and_then(|&key| {
+            viqr_tone_key_to_internal(key).and_then(|tone| apply_tone(letter, Some(tone)))
+        }) {
+            letter = toned;
+            index += 1;
+        }
+        output.push(letter);
+    }
+    output
+}
+
+// ==================== HTML Entities and \u Escapes ====================
+
+/// Encodes `text` like the engine output, writing `escape` as `escaped` so it decodes unchanged.
+fn encode_escaped(text: &str, escape: char, escaped: &str, encoding: OutputEncoding) -> String {
+    let mut output = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch == escape {
+            output.push_str(escaped);
+        } else {
+            output.push_str(&encode_char(ch, encoding));
+        }
+    }
+    output
+}
+
+fn decode_html_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match parse_html_entity(rest) {
+            Some((ch, len)) => {
+                output.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Parses the entity at the start of `text`, returning the character and the entity length.
+fn parse_html_entity(text: &str) -> Option<(char, usize)> {
+    let end = text.find(';')?;
+    let ch = match &text[1..end] {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        body => {
+            let number = body.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((ch, end + 1))
+}
+
+fn decode_unicode_escapes(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut units: Vec<u16> = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("\\u") {
+            if let Some(unit) = after
+                .get(..4)
+                .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+            {
+                units.push(unit);
+                rest = &after[4..];
+                continue;
+            }
+        }
+
+        // Surrogate pairs arrive as two escapes; decode once the run of escapes ends.
+        output.extend(char::decode_utf16(units.drain(..)).map(|ch| ch.unwrap_or('\u{FFFD}')));
+        if let Some(after) = rest.strip_prefix("\\\\") {
+            output.push('\\');
+            rest = after;
+            continue;
+        }
+        let ch = rest.chars().next().unwrap_or_default();
+        output.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    output.extend(char::decode_utf16(units.drain(..)).map(|ch| ch.unwrap_or('\u{FFFD}')));
+    output
+}
+
+// ==================== Detection ====================
+
+/// Scores how much `text` looks like Vietnamese: every Vietnamese letter in a well-formed word
+/// counts for the encoding, every letter in a malformed word and every stray non-ASCII
+/// character counts against it.
+fn plausibility(text: &str) -> i64 {
+    text.split(|ch: char| !ch.is_alphabetic() && !is_combining_mark(ch))
+        .map(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            let vietnamese = chars
+                .iter()
+                .filter(|&&ch| !ch.is_ascii() && is_vietnamese_letter(ch))
+                .count() as i64;
+            let stray = chars
+                .iter()
+                .filter(|&&ch| !ch.is_ascii() && !is_vietnamese_letter(ch))
+                .count() as i64;
+            if is_well_formed_word(&chars) {
+                vietnamese - 2 * stray
+            } else {
+                -vietnamese - 2 * stray
+            }
+        })
+        .sum()
+}
+
+fn is_vietnamese_letter(ch: char) -> bool {
+    matches!(ch, 'đ' | 'Đ') || TONED_TO_BASE.contains_key(&ch) || VOWEL_TO_TONED.contains_key(&ch)
+}
+
+/// A syllable [`Syllable::parse`] accepts (or no vowel at all), with no capital after a
+/// lowercase letter.
+fn is_well_formed_word(chars: &[char]) -> bool {
+    if chars
+        .windows(2)
+        .any(|pair| pair[0].is_lowercase() && pair[1].is_uppercase())
+    {
+        return false;
+    }
+
+    // Words without a vowel are abbreviations or consonant-only fragments.
+    !chars.iter().any(|&ch| is_vowel(ch))
+        || Syllable::parse(&chars.iter().collect::<String>()).is_some()
+}
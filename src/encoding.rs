@@ -0,0 +1,648 @@
+//! Output encodings other than Unicode.
+//!
+//! Legacy code pages are emitted one `char` per byte, using the code point equal to the byte
+//! value (U+0000..=U+00FF). This is what hosts hand to applications that render legacy Vietnamese
+//! fonts. NFD, VNI-Windows, Windows-1258 and the ASCII encodings (VIQR, HTML entities, `\u`
+//! escapes) spell one letter with several characters, so the engine remembers the output text of
+//! each emitted letter and measures `delete_count` on it. Host backspaces are replayed on that
+//! text one [`DeleteCountUnit`] at a time, so a letter the host only partly deleted keeps the rest.
+
+use std::fmt::Write;
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::common::{lower_char, DeleteCountUnit, KeyTransformAction, OutputEncoding};
+use crate::diacritics::split_vowel_and_tone;
+use crate::VitypeEngine;
+
+/// How many emitted characters [`VitypeEngine`] remembers. Rewrites never reach further back than
+/// the words kept in the engine history.
+const EMITTED_TEXT_LIMIT: usize = 256;
+
+/// Converts engine output (precomposed Unicode) to `encoding`.
+pub(crate) fn encode_output(text: String, encoding: OutputEncoding) -> String {
+    if encoding == OutputEncoding::Unicode {
+        return text;
+    }
+    let mut output = String::with_capacity(text.len());
+    for ch in text.chars() {
+        push_encoded_char(&mut output, ch, encoding);
+    }
+    output
+}
+
+pub(crate) fn encode_char(ch: char, encoding: OutputEncoding) -> String {
+    let mut output = String::new();
+    push_encoded_char(&mut output, ch, encoding);
+    output
+}
+
+/// Output text of one emitted character, with the encoding it went out in.
+#[derive(Clone, Debug)]
+pub(crate) struct EmittedText {
+    text: String,
+    encoding: OutputEncoding,
+}
+
+impl EmittedText {
+    /// Size in the host: one byte per character in the code pages, UTF-8 bytes otherwise.
+    fn byte_len(&self) -> usize {
+        if is_code_page(self.encoding) {
+            self.text.chars().count()
+        } else {
+            self.text.len()
+        }
+    }
+}
+
+fn is_code_page(encoding: OutputEncoding) -> bool {
+    matches!(
+        encoding,
+        OutputEncoding::Tcvn3
+            | OutputEncoding::Viscii
+            | OutputEncoding::VniWindows
+            | OutputEncoding::Cp1258
+    )
+}
+
+/// Length of `emitted` in `unit`.
+fn measure(emitted: &[EmittedText], unit: DeleteCountUnit) -> usize {
+    match unit {
+        DeleteCountUnit::CodePoints => emitted.iter().map(|e| e.text.chars().count()).sum(),
+        DeleteCountUnit::Utf16CodeUnits => {
+            emitted.iter().map(|e| e.text.encode_utf16().count()).sum()
+        }
+        DeleteCountUnit::Utf8Bytes => emitted.iter().map(EmittedText::byte_len).sum(),
+        DeleteCountUnit::GraphemeClusters => emitted
+            .iter()
+            .map(|e| e.text.as_str())
+            .collect::<String>()
+            .graphemes(true)
+            .count(),
+    }
+}
+
+/// Appends `ch` in `encoding`. Code pages write `?` for characters they have no byte for.
+fn push_encoded_char(output: &mut String, ch: char, encoding: OutputEncoding) {
+    match encoding {
+        OutputEncoding::Unicode => output.push(ch),
+        OutputEncoding::CompositeUnicode => output.extend(ch.nfd()),
+        OutputEncoding::Tcvn3 => output.push(code_page_char(ch, tcvn3_byte(ch))),
+        OutputEncoding::Viscii => output.push(code_page_char(ch, viscii_byte(ch))),
+        OutputEncoding::VniWindows => output.extend(vni_windows_chars(ch)),
+        OutputEncoding::Cp1258 => push_cp1258(output, ch),
+        OutputEncoding::Viqr => push_viqr(output, ch),
+        OutputEncoding::HtmlEntity if !ch.is_ascii() => {
+            let _ = write!(output, "&#{};", ch as u32);
+        }
+        OutputEncoding::UnicodeEscape if !ch.is_ascii() => {
+            for unit in ch.encode_utf16(&mut [0; 2]) {
+                let _ = write!(output, "\\u{:04X}", unit);
+            }
+        }
+        OutputEncoding::HtmlEntity | OutputEncoding::UnicodeEscape => output.push(ch),
+    }
+}
+
+/// Code page character for `ch`, given its byte in the code page's table. ASCII without an entry
+/// is written as is; anything else becomes `?`, since its own code point would decode as a
+/// different letter.
+fn code_page_char(ch: char, byte: Option<u8>) -> char {
+    match byte {
+        Some(byte) => char::from(byte),
+        None if ch.is_ascii() => ch,
+        None => '?',
+    }
+}
+
+// ==================== Encoded Output on VitypeEngine ====================
+
+impl VitypeEngine {
+    /// Like [`process`](Self::process), but `text` is in the configured output encoding and
+    /// `delete_count` is measured in the configured [`DeleteCountUnit`].
+    ///
+    /// The engine remembers the output text of every character it emitted, so a rewrite deletes
+    /// exactly what is on screen even if the encoding or unit changed since. Keys the host inserts
+    /// itself (a `None` result) are remembered as typed.
+    pub fn process_encoded(&mut self, input: &str) -> Option<KeyTransformAction> {
+        let Some(action) = self.process(input) else {
+            self.record_emitted(input.chars().map(String::from));
+            return None;
+        };
+
+        let kept = self.emitted_text.len().saturating_sub(action.delete_count);
+        let unknown = action.delete_count.saturating_sub(self.emitted_text.len());
+        let deleted: Vec<EmittedText> = self.emitted_text.drain(kept..).collect();
+        let delete_count = measure(&deleted, self.config.delete_count_unit) + unknown;
+
+        let encoding = self.config.output_encoding;
+        self.record_emitted(action.text.chars().map(|ch| encode_char(ch, encoding)));
+        Some(KeyTransformAction {
+            delete_count,
+            text: encode_output(action.text, encoding),
+        })
+    }
+
+    fn record_emitted(&mut self, text: impl Iterator<Item = String>) {
+        let encoding = self.config.output_encoding;
+        self.emitted_text
+            .extend(text.map(|text| EmittedText { text, encoding }));
+        let excess = self.emitted_text.len().saturating_sub(EMITTED_TEXT_LIMIT);
+        self.emitted_text.drain(..excess);
+    }
+
+    /// Removes what one host backspace deletes from the remembered output: the last grapheme
+    /// cluster with [`DeleteCountUnit::GraphemeClusters`], the last character otherwise.
+    pub(crate) fn record_backspace(&mut self) {
+        let Some(last) = self.emitted_text.last_mut() else {
+            return;
+        };
+        let start = match self.config.delete_count_unit {
+            DeleteCountUnit::GraphemeClusters => last
+                .text
+                .grapheme_indices(true)
+                .next_back()
+                .map(|(index, _)| index),
+            _ => last.text.char_indices().next_back().map(|(index, _)| index),
+        };
+        last.text.truncate(start.unwrap_or(0));
+        if last.text.is_empty() {
+            self.emitted_text.pop();
+        }
+    }
+}
+
+// ==================== TCVN3 (ABC) ====================
+
+/// TCVN 5712:1993 VN3, the "ABC" font encoding.
+///
+/// VN3 has no uppercase toned letters: ABC fonts come in pairs, and the uppercase font (`.VnTimeH`
+/// and friends) draws the lowercase codes as capitals. Uppercase toned letters therefore map to the
+/// lowercase code; the seven uppercase base letters (Ă Â Ê Ô Ơ Ư Đ) have codes of their own.
+fn tcvn3_byte(ch: char) -> Option<u8> {
+    Some(match ch {
+        'á' => 0xB8,
+        'à' => 0xB5,
+        'ả' => 0xB6,
+        'ã' => 0xB7,
+        'ạ' => 0xB9,
+        'ă' => 0xA8,
+        'ắ' => 0xBE,
+        'ằ' => 0xBB,
+        'ẳ' => 0xBC,
+        'ẵ' => 0xBD,
+        'ặ' => 0xC6,
+        'â' => 0xA9,
+        'ấ' => 0xCA,
+        'ầ' => 0xC7,
+        'ẩ' => 0xC8,
+        'ẫ' => 0xC9,
+        'ậ' => 0xCB,
+        'é' => 0xD0,
+        'è' => 0xCC,
+        'ẻ' => 0xCE,
+        'ẽ' => 0xCF,
+        'ẹ' => 0xD1,
+        'ê' => 0xAA,
+        'ế' => 0xD5,
+        'ề' => 0xD2,
+        'ể' => 0xD3,
+        'ễ' => 0xD4,
+        'ệ' => 0xD6,
+        'í' => 0xDD,
+        'ì' => 0xD7,
+        'ỉ' => 0xD8,
+        'ĩ' => 0xDC,
+        'ị' => 0xDE,
+        'ó' => 0xE3,
+        'ò' => 0xDF,
+        'ỏ' => 0xE1,
+        'õ' => 0xE2,
+        'ọ' => 0xE4,
+        'ô' => 0xAB,
+        'ố' => 0xE8,
+        'ồ' => 0xE5,
+        'ổ' => 0xE6,
+        'ỗ' => 0xE7,
+        'ộ' => 0xE9,
+        'ơ' => 0xAC,
+        'ớ' => 0xED,
+        'ờ' => 0xEA,
+        'ở' => 0xEB,
+        'ỡ' => 0xEC,
+        'ợ' => 0xEE,
+        'ú' => 0xF3,
+        'ù' => 0xEF,
+        'ủ' => 0xF1,
+        'ũ' => 0xF2,
+        'ụ' => 0xF4,
+        'ư' => 0xAD,
+        'ứ' => 0xF8,
+        'ừ' => 0xF5,
+        'ử' => 0xF6,
+        'ữ' => 0xF7,
+        'ự' => 0xF9,
+        'ý' => 0xFD,
+        'ỳ' => 0xFA,
+        'ỷ' => 0xFB,
+        'ỹ' => 0xFC,
+        'ỵ' => 0xFE,
+        'đ' => 0xAE,
+        'Á' => 0xB8,
+        'À' => 0xB5,
+        'Ả' => 0xB6,
+        'Ã' => 0xB7,
+        'Ạ' => 0xB9,
+        'Ă' => 0xA1,
+        'Ắ' => 0xBE,
+        'Ằ' => 0xBB,
+        'Ẳ' => 0xBC,
+        'Ẵ' => 0xBD,
+        'Ặ' => 0xC6,
+        'Â' => 0xA2,
+        'Ấ' => 0xCA,
+        'Ầ' => 0xC7,
+        'Ẩ' => 0xC8,
+        'Ẫ' => 0xC9,
+        'Ậ' => 0xCB,
+        'É' => 0xD0,
+        'È' => 0xCC,
+        'Ẻ' => 0xCE,
+        'Ẽ' => 0xCF,
+        'Ẹ' => 0xD1,
+        'Ê' => 0xA3,
+        'Ế' => 0xD5,
+        'Ề' => 0xD2,
+        'Ể' => 0xD3,
+        'Ễ' => 0xD4,
+        'Ệ' => 0xD6,
+        'Í' => 0xDD,
+        'Ì' => 0xD7,
+        'Ỉ' => 0xD8,
+        'Ĩ' => 0xDC,
+        'Ị' => 0xDE,
+        'Ó' => 0xE3,
+        'Ò' => 0xDF,
+        'Ỏ' => 0xE1,
+        'Õ' => 0xE2,
+        'Ọ' => 0xE4,
+        'Ô' => 0xA4,
+        'Ố' => 0xE8,
+        'Ồ' => 0xE5,
+        'Ổ' => 0xE6,
+        'Ỗ' => 0xE7,
+        'Ộ' => 0xE9,
+        'Ơ' => 0xA5,
+        'Ớ' => 0xED,
+        'Ờ' => 0xEA,
+        'Ở' => 0xEB,
+        'Ỡ' => 0xEC,
+        'Ợ' => 0xEE,
+        'Ú' => 0xF3,
+        'Ù' => 0xEF,
+        'Ủ' => 0xF1,
+        'Ũ' => 0xF2,
+        'Ụ' => 0xF4,
+        'Ư' => 0xA6,
+        'Ứ' => 0xF8,
+        'Ừ' => 0xF5,
+        'Ử' => 0xF6,
+        'Ữ' => 0xF7,
+        'Ự' => 0xF9,
+        'Ý' => 0xFD,
+        'Ỳ' => 0xFA,
+        'Ỷ' => 0xFB,
+        'Ỹ' => 0xFC,
+        'Ỵ' => 0xFE,
+        'Đ' => 0xA7,
+        _ => return None,
+    })
+}
+
+// ==================== VISCII ====================
+
+/// VISCII (RFC 1456). All 134 precomposed Vietnamese letters have a code; six of the uppercase ones
+/// replace C0 control codes.
+fn viscii_byte(ch: char) -> Option<u8> {
+    Some(match ch {
+        'á' => 0xE1,
+        'à' => 0xE0,
+        'ả' => 0xE4,
+        'ã' => 0xE3,
+        'ạ' => 0xD5,
+        'ă' => 0xE5,
+        'ắ' => 0xA1,
+        'ằ' => 0xA2,
+        'ẳ' => 0xC6,
+        'ẵ' => 0xC7,
+        'ặ' => 0xA3,
+        'â' => 0xE2,
+        'ấ' => 0xA4,
+        'ầ' => 0xA5,
+        'ẩ' => 0xA6,
+        'ẫ' => 0xE7,
+        'ậ' => 0xA7,
+        'é' => 0xE9,
+        'è' => 0xE8,
+        'ẻ' => 0xEB,
+        'ẽ' => 0xA8,
+        'ẹ' => 0xA9,
+        'ê' => 0xEA,
+        'ế' => 0xAA,
+        'ề' => 0xAB,
+        'ể' => 0xAC,
+        'ễ' => 0xAD,
+        'ệ' => 0xAE,
+        'í' => 0xED,
+        'ì' => 0xEC,
+        'ỉ' => 0xEF,
+        'ĩ' => 0xEE,
+        'ị' => 0xB8,
+        'ó' => 0xF3,
+        'ò' => 0xF2,
+        'ỏ' => 0xF6,
+        'õ' => 0xF5,
+        'ọ' => 0xF7,
+        'ô' => 0xF4,
+        'ố' => 0xAF,
+        'ồ' => 0xB0,
+        'ổ' => 0xB1,
+        'ỗ' => 0xB2,
+        'ộ' => 0xB5,
+        'ơ' => 0xBD,
+        'ớ' => 0xBE,
+        'ờ' => 0xB6,
+        'ở' => 0xB7,
+        'ỡ' => 0xDE,
+        'ợ' => 0xFE,
+        'ú' => 0xFA,
+        'ù' => 0xF9,
+        'ủ' => 0xFC,
+        'ũ' => 0xFB,
+        'ụ' => 0xF8,
+        'ư' => 0xDF,
+        'ứ' => 0xD1,
+        'ừ' => 0xD7,
+        'ử' => 0xD8,
+        'ữ' => 0xE6,
+        'ự' => 0xF1,
+        'ý' => 0xFD,
+        'ỳ' => 0xCF,
+        'ỷ' => 0xD6,
+        'ỹ' => 0xDB,
+        'ỵ' => 0xDC,
+        'đ' => 0xF0,
+        'Á' => 0xC1,
+        'À' => 0xC0,
+        'Ả' => 0xC4,
+        'Ã' => 0xC3,
+        'Ạ' => 0x80,
+        'Ă' => 0xC5,
+        'Ắ' => 0x81,
+        'Ằ' => 0x82,
+        'Ẳ' => 0x02,
+        'Ẵ' => 0x05,
+        'Ặ' => 0x83,
+        'Â' => 0xC2,
+        'Ấ' => 0x84,
+        'Ầ' => 0x85,
+        'Ẩ' => 0x86,
+        'Ẫ' => 0x06,
+        'Ậ' => 0x87,
+        'É' => 0xC9,
+        'È' => 0xC8,
+        'Ẻ' => 0xCB,
+        'Ẽ' => 0x88,
+        'Ẹ' => 0x89,
+        'Ê' => 0xCA,
+        'Ế' => 0x8A,
+        'Ề' => 0x8B,
+        'Ể' => 0x8C,
+        'Ễ' => 0x8D,
+        'Ệ' => 0x8E,
+        'Í' => 0xCD,
+        'Ì' => 0xCC,
+        'Ỉ' => 0x9B,
+        'Ĩ' => 0xCE,
+        'Ị' => 0x98,
+        'Ó' => 0xD3,
+        'Ò' => 0xD2,
+        'Ỏ' => 0x99,
+        'Õ' => 0xA0,
+        'Ọ' => 0x9A,
+        'Ô' => 0xD4,
+        'Ố' => 0x8F,
+        'Ồ' => 0x90,
+        'Ổ' => 0x91,
+        'Ỗ' => 0x92,
+        'Ộ' => 0x93,
+        'Ơ' => 0xB4,
+        'Ớ' => 0x95,
+        'Ờ' => 0x96,
+        'Ở' => 0x97,
+        'Ỡ' => 0xB3,
+        'Ợ' => 0x94,
+        'Ú' => 0xDA,
+        'Ù' => 0xD9,
+        'Ủ' => 0x9C,
+        'Ũ' => 0x9D,
+        'Ụ' => 0x9E,
+        'Ư' => 0xBF,
+        'Ứ' => 0xBA,
+        'Ừ' => 0xBB,
+        'Ử' => 0xBC,
+        'Ữ' => 0xFF,
+        'Ự' => 0xB9,
+        'Ý' => 0xDD,
+        'Ỳ' => 0x9F,
+        'Ỷ' => 0x14,
+        'Ỹ' => 0x19,
+        'Ỵ' => 0x1E,
+        'Đ' => 0xD0,
+        _ => return None,
+    })
+}
+
+// ==================== VNI-Windows ====================
+
+/// VNI-Windows (VNI-Times and friends): most letters are an ASCII base letter followed by a
+/// diacritic byte. `ơ`, `ư`, `đ` and toned `i` (and `ỵ`) have bytes of their own; `ơ`/`ư` still
+/// take a separate tone byte. Uppercase letters use the lowercase bytes minus 0x20.
+fn vni_windows_chars(ch: char) -> impl Iterator<Item = char> {
+    let (base, mark) = vni_windows_bytes(ch).unwrap_or((code_page_char(ch, None), None));
+    std::iter::once(base).chain(mark.map(char::from))
+}
+
+fn vni_windows_bytes(ch: char) -> Option<(char, Option<u8>)> {
+    let (shaped, tone) = split_vowel_and_tone(ch);
+    let upper = shaped.is_uppercase();
+    let cased_byte = |byte: u8| if upper { byte - 0x20 } else { byte };
+    let cased_letter = |letter: char| {
+        if upper {
+            letter.to_ascii_uppercase()
+        } else {
+            letter
+        }
+    };
+    let tone_byte = tone.map(|tone| cased_byte(vni_windows_tone_byte(tone)));
+
+    Some(match (lower_char(shaped), tone) {
+        ('i', Some(tone)) => (char::from(cased_byte(vni_windows_i_byte(tone))), None),
+        ('y', Some('j')) => (char::from(cased_byte(0xEE)), None),
+        ('a' | 'e' | 'o' | 'u' | 'y', Some(_)) => (shaped, tone_byte),
+        ('â', _) => (
+            cased_letter('a'),
+            Some(cased_byte(vni_windows_circumflex_byte(tone))),
+        ),
+        ('ê', _) => (
+            cased_letter('e'),
+            Some(cased_byte(vni_windows_circumflex_byte(tone))),
+        ),
+        ('ô', _) => (
+            cased_letter('o'),
+            Some(cased_byte(vni_windows_circumflex_byte(tone))),
+        ),
+        ('ă', _) => (
+            cased_letter('a'),
+            Some(cased_byte(vni_windows_breve_byte(tone))),
+        ),
+        ('ơ', _) => (char::from(cased_byte(0xF4)), tone_byte),
+        ('ư', _) => (char::from(cased_byte(0xF6)), tone_byte),
+        ('đ', None) => (char::from(cased_byte(0xF1)), None),
+        _ => return None,
+    })
+}
+
+/// Tone byte after a plain vowel, `ơ` or `ư`.
+fn vni_windows_tone_byte(tone: char) -> u8 {
+    match tone {
+        's' => 0xF9,
+        'f' => 0xF8,
+        'r' => 0xFB,
+        'x' => 0xF5,
+        _ => 0xEF,
+    }
+}
+
+/// Toned `i` is a single byte.
+fn vni_windows_i_byte(tone: char) -> u8 {
+    match tone {
+        's' => 0xED,
+        'f' => 0xEC,
+        'r' => 0xE6,
+        'x' => 0xF3,
+        _ => 0xF2,
+    }
+}
+
+/// Circumflex combined with the tone, after `a`/`e`/`o`.
+fn vni_windows_circumflex_byte(tone: Option<char>) -> u8 {
+    match tone {
+        None => 0xE2,
+        Some('s') => 0xE1,
+        Some('f') => 0xE0,
+        Some('r') => 0xE5,
+        Some('x') => 0xE3,
+        Some(_) => 0xE4,
+    }
+}
+
+/// Breve combined with the tone, after `a`.
+fn vni_windows_breve_byte(tone: Option<char>) -> u8 {
+    match tone {
+        None => 0xEA,
+        Some('s') => 0xE9,
+        Some('f') => 0xE8,
+        Some('r') => 0xFA,
+        Some('x') => 0xFC,
+        Some(_) => 0xEB,
+    }
+}
+
+// ==================== Windows-1258 ====================
+
+/// Windows-1258: shaped letters (ă â ê ô ơ ư đ) are single bytes, and the tone always follows as
+/// a combining-mark byte, so `ệ` is `ê` + dot below.
+fn push_cp1258(output: &mut String, ch: char) {
+    let (shaped, tone) = split_vowel_and_tone(ch);
+    output.push(code_page_char(shaped, cp1258_letter_byte(shaped)));
+    if let Some(tone) = tone {
+        output.push(char::from(cp1258_tone_byte(tone)));
+    }
+}
+
+fn cp1258_letter_byte(ch: char) -> Option<u8> {
+    Some(match ch {
+        'ă' => 0xE3,
+        'â' => 0xE2,
+        'ê' => 0xEA,
+        'ô' => 0xF4,
+        'ơ' => 0xF5,
+        'ư' => 0xFD,
+        'đ' => 0xF0,
+        'Ă' => 0xC3,
+        'Â' => 0xC2,
+        'Ê' => 0xCA,
+        'Ô' => 0xD4,
+        'Ơ' => 0xD5,
+        'Ư' => 0xDD,
+        'Đ' => 0xD0,
+        _ => return None,
+    })
+}
+
+fn cp1258_tone_byte(tone: char) -> u8 {
+    match tone {
+        's' => 0xEC, // U+0301
+        'f' => 0xCC, // U+0300
+        'r' => 0xD2, // U+0309
+        'x' => 0xDE, // U+0303
+        _ => 0xF2,   // U+0323
+    }
+}
+
+// ==================== VIQR ====================
+
+/// VIQR (RFC 1456): the base letter, then the shape mark (`(` `^` `+`), then the tone mark.
+/// `đ` is written `dd`.
+fn push_viqr(output: &mut String, ch: char) {
+    let (shaped, tone) = split_vowel_and_tone(ch);
+    match viqr_shaped_letter(shaped) {
+        Some(letter) => output.push_str(letter),
+        None => output.push(shaped),
+    }
+    if let Some(tone) = tone {
+        output.push(viqr_tone_mark(tone));
+    }
+}
+
+fn viqr_shaped_letter(ch: char) -> Option<&'static str> {
+    Some(match ch {
+        'ă' => "a(",
+        'â' => "a^",
+        'ê' => "e^",
+        'ô' => "o^",
+        'ơ' => "o+",
+        'ư' => "u+",
+        'đ' => "dd",
+        'Ă' => "A(",
+        'Â' => "A^",
+        'Ê' => "E^",
+        'Ô' => "O^",
+        'Ơ' => "O+",
+        'Ư' => "U+",
+        'Đ' => "DD",
+        _ => return None,
+    })
+}
+
+fn viqr_tone_mark(tone: char) -> char {
+    match tone {
+        's' => '\'',
+        'f' => '`',
+        'r' => '?',
+        'x' => '~',
+        _ => '.',
+    }
+}
@@ -0,0 +1,71 @@
+//! English words typed as-is when `english_bypass` is enabled.
+//!
+//! A built-in list of common English words that Telex would mangle (`mass` → `mas`,
+//! `week` → `wêk`) is embedded in the library. Users add their own with
+//! [`VitypeEngine::add_english_words`] or from a word list file:
+//!
+//! ```text
+//! # One word per line; blank lines and lines starting with `#` are ignored.
+//! door
+//! Dockerfile
+//! ```
+//!
+//! Words match case-insensitively against the keys typed for the current word.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+
+use crate::settings::SettingsError;
+use crate::VitypeEngine;
+
+/// Keys at the start of a word that are enough to call it English at the word boundary.
+const MIN_PREFIX_MATCH_LEN: usize = 4;
+
+static BUILT_IN_WORDS: Lazy<HashSet<&'static str>> =
+    Lazy::new(|| word_list_entries(include_str!("english_words.txt")).collect());
+
+fn word_list_entries(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+impl VitypeEngine {
+    /// Adds words to the English bypass list used when
+    /// [`set_english_bypass`](Self::set_english_bypass) is enabled. Matching ignores case.
+    pub fn add_english_words<I, S>(&mut self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let word_lists = Arc::make_mut(&mut self.word_lists);
+        word_lists
+            .english_words
+            .extend(words.into_iter().map(|word| word.as_ref().to_lowercase()));
+    }
+
+    /// Adds the words of a word list file (one per line, `#` comments) to the English bypass
+    /// list.
+    pub fn load_english_words(&mut self, path: impl AsRef<Path>) -> Result<(), SettingsError> {
+        let text = fs::read_to_string(path)?;
+        self.add_english_words(word_list_entries(&text));
+        Ok(())
+    }
+
+    pub(super) fn is_english_word(&self, keys: &[char]) -> bool {
+        let word: String = keys.iter().flat_map(|ch| ch.to_lowercase()).collect();
+        BUILT_IN_WORDS.contains(word.as_str()) || self.word_lists.english_words.contains(&word)
+    }
+
+    /// Whether the raw keys of the finished word are an English word, or start with one that is
+    /// long enough to be sure (`bosses` after editing `boss` with backspace).
+    pub(super) fn is_english_word_at_boundary(&self) -> bool {
+        (MIN_PREFIX_MATCH_LEN..=self.raw_buffer.len())
+            .any(|len| self.is_english_word(&self.raw_buffer[..len]))
+            || self.is_english_word(&self.raw_buffer)
+    }
+}
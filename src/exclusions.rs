@@ -0,0 +1,313 @@
+//! Words and prefixes that are never transformed.
+//!
+//! An [`ExclusionList`] names tokens such as `async`, `goto` or `kubectl` that the engine
+//! always types as-is. Once the keys typed for a word start with a prefix pattern, the word
+//! switches to foreign mode and is shown exactly as typed. Whole-word patterns are checked when
+//! the word ends, so `ma` does not stop `mas` from becoming `má`. Lists are saved as:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [[exclude]]
+//! word = "goto"                     # the whole word
+//!
+//! [[exclude]]
+//! prefix = "kubectl"                # any word starting with it
+//! case_sensitive = true             # optional, default false
+//! ```
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{is_json_path, SettingsError, SETTINGS_VERSION};
+use crate::VitypeEngine;
+
+/// How an [`ExclusionPattern`] matches the keys of a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExclusionKind {
+    /// The keys spell the whole pattern.
+    Exact,
+    /// The keys start with the pattern.
+    Prefix,
+}
+
+/// One entry of an [`ExclusionList`]. Patterns ignore case unless made case-sensitive.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExclusionPattern {
+    text: String,
+    kind: ExclusionKind,
+    case_sensitive: bool,
+}
+
+impl ExclusionPattern {
+    pub fn exact(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            kind: ExclusionKind::Exact,
+            case_sensitive: false,
+        }
+    }
+
+    pub fn prefix(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            kind: ExclusionKind::Prefix,
+            case_sensitive: false,
+        }
+    }
+
+    pub fn case_sensitive(mut self, enabled: bool) -> Self {
+        self.case_sensitive = enabled;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn kind(&self) -> ExclusionKind {
+        self.kind
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Whether the keys typed for a word match this pattern.
+    pub fn matches(&self, keys: &str) -> bool {
+        let (keys, text) = if self.case_sensitive {
+            (keys.to_string(), self.text.clone())
+        } else {
+            (keys.to_lowercase(), self.text.to_lowercase())
+        };
+        match self.kind {
+            ExclusionKind::Exact => keys == text,
+            ExclusionKind::Prefix => keys.starts_with(&text),
+        }
+    }
+}
+
+/// Patterns for words the engine never transforms. The file format is described in
+/// `src/exclusions.rs`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExclusionList {
+    patterns: Vec<ExclusionPattern>,
+}
+
+impl ExclusionList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pattern. Returns false if the list already has it. Empty patterns are ignored.
+    pub fn add(&mut self, pattern: ExclusionPattern) -> bool {
+        if pattern.text.is_empty() || self.patterns.contains(&pattern) {
+            return false;
+        }
+        self.patterns.push(pattern);
+        true
+    }
+
+    /// Removes a pattern. Returns false if the list does not have it.
+    pub fn remove(&mut self, pattern: &ExclusionPattern) -> bool {
+        let count = self.patterns.len();
+        self.patterns.retain(|existing| existing != pattern);
+        self.patterns.len() != count
+    }
+
+    pub fn clear(&mut self) {
+        self.patterns.clear();
+    }
+
+    pub fn patterns(&self) -> &[ExclusionPattern] {
+        &self.patterns
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether any pattern matches the keys typed for a word.
+    pub fn matches(&self, keys: &str) -> bool {
+        self.patterns.iter().any(|pattern| pattern.matches(keys))
+    }
+
+    /// Whether any prefix pattern matches the keys typed so far for a word.
+    fn matches_prefix(&self, keys: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.kind == ExclusionKind::Prefix && pattern.matches(keys))
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, SettingsError> {
+        let file: ExclusionFile =
+            toml::from_str(text).map_err(|err| SettingsError::Parse(err.to_string()))?;
+        file.into_list()
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(&ExclusionFile::from_list(self))
+            .expect("exclusion lists always serialize to TOML")
+    }
+
+    pub fn from_json_str(text: &str) -> Result<Self, SettingsError> {
+        let file: ExclusionFile =
+            serde_json::from_str(text).map_err(|err| SettingsError::Parse(err.to_string()))?;
+        file.into_list()
+    }
+
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(&ExclusionFile::from_list(self))
+            .expect("exclusion lists always serialize to JSON")
+    }
+
+    /// Reads an exclusion list file. Files ending in `.json` are parsed as JSON, anything else
+    /// as TOML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        if is_json_path(path) {
+            Self::from_json_str(&text)
+        } else {
+            Self::from_toml_str(&text)
+        }
+    }
+
+    /// Writes an exclusion list file, choosing JSON or TOML from the extension like
+    /// [`load`](Self::load).
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SettingsError> {
+        let path = path.as_ref();
+        let text = if is_json_path(path) {
+            self.to_json_string()
+        } else {
+            self.to_toml_string()
+        };
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+// ==================== Engine ====================
+
+impl VitypeEngine {
+    /// Replaces the exclusion list. Words matching it are always typed as-is.
+    pub fn set_exclusions(&mut self, exclusions: ExclusionList) {
+        Arc::make_mut(&mut self.word_lists).exclusions = exclusions;
+    }
+
+    /// The current exclusion list.
+    pub fn exclusions(&self) -> &ExclusionList {
+        &self.word_lists.exclusions
+    }
+
+    /// Adds a pattern to the exclusion list. Returns false if the list already has it.
+    pub fn add_exclusion(&mut self, pattern: ExclusionPattern) -> bool {
+        Arc::make_mut(&mut self.word_lists).exclusions.add(pattern)
+    }
+
+    /// Removes a pattern from the exclusion list. Returns false if the list does not have it.
+    pub fn remove_exclusion(&mut self, pattern: &ExclusionPattern) -> bool {
+        Arc::make_mut(&mut self.word_lists)
+            .exclusions
+            .remove(pattern)
+    }
+
+    /// Replaces the exclusion list with the one in an exclusion list file.
+    pub fn load_exclusions(&mut self, path: impl AsRef<Path>) -> Result<(), SettingsError> {
+        self.set_exclusions(ExclusionList::load(path)?);
+        Ok(())
+    }
+
+    /// Writes the exclusion list to an exclusion list file.
+    pub fn save_exclusions(&self, path: impl AsRef<Path>) -> Result<(), SettingsError> {
+        self.word_lists.exclusions.save(path)
+    }
+
+    /// Whether the keys of a finished word match any pattern.
+    pub(super) fn is_excluded(&self, keys: &[char]) -> bool {
+        if self.word_lists.exclusions.is_empty() {
+            return false;
+        }
+        let keys: String = keys.iter().collect();
+        self.word_lists.exclusions.matches(&keys)
+    }
+
+    /// Whether the keys typed so far for a word start with a prefix pattern. Whole-word
+    /// patterns wait for the word to end, since more keys may follow.
+    pub(super) fn is_excluded_prefix(&self, keys: &[char]) -> bool {
+        if self.word_lists.exclusions.is_empty() {
+            return false;
+        }
+        let keys: String = keys.iter().collect();
+        self.word_lists.exclusions.matches_prefix(&keys)
+    }
+}
+
+// ==================== Serialized Form ====================
+
+#[derive(Serialize, Deserialize, Default)]
+struct ExclusionFile {
+    version: Option<u32>,
+    #[serde(default)]
+    exclude: Vec<ExclusionEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExclusionEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    word: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    case_sensitive: Option<bool>,
+}
+
+impl ExclusionFile {
+    fn from_list(list: &ExclusionList) -> Self {
+        Self {
+            version: Some(SETTINGS_VERSION),
+            exclude: list
+                .patterns
+                .iter()
+                .map(|pattern| {
+                    let text = Some(pattern.text.clone());
+                    let (word, prefix) = match pattern.kind {
+                        ExclusionKind::Exact => (text, None),
+                        ExclusionKind::Prefix => (None, text),
+                    };
+                    ExclusionEntry {
+                        word,
+                        prefix,
+                        case_sensitive: pattern.case_sensitive.then_some(true),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn into_list(self) -> Result<ExclusionList, SettingsError> {
+        let version = self.version.ok_or(SettingsError::MissingVersion)?;
+        if version > SETTINGS_VERSION {
+            return Err(SettingsError::UnsupportedVersion(version));
+        }
+
+        let mut list = ExclusionList::new();
+        for entry in self.exclude {
+            let pattern = match (entry.word, entry.prefix) {
+                (Some(word), None) if !word.is_empty() => ExclusionPattern::exact(word),
+                (None, Some(prefix)) if !prefix.is_empty() => ExclusionPattern::prefix(prefix),
+                (word, prefix) => {
+                    return Err(SettingsError::InvalidValue {
+                        key: "exclude",
+                        value: word.or(prefix).unwrap_or_default(),
+                    })
+                }
+            };
+            list.add(pattern.case_sensitive(entry.case_sensitive.unwrap_or(false)));
+        }
+        Ok(list)
+    }
+}
@@ -1,11 +1,21 @@
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::ptr;
+use std::sync::Arc;
 
-use unicode_normalization::UnicodeNormalization;
+use crate::common::{DeleteCountUnit, InputMethod, OutputEncoding, TonePlacement};
+use crate::settings::SettingsError;
+use crate::{
+    convert_text, detect_encoding, remove_diacritics, remove_tones, slugify, EngineConfig,
+    ExclusionPattern, Keymap, VitypeEngine,
+};
 
-use crate::common::{InputMethod, OutputEncoding, TonePlacement};
-use crate::VitypeEngine;
+const SETTINGS_OK: i32 = 0;
+const SETTINGS_INVALID_ARGUMENT: i32 = -1;
+const SETTINGS_IO_ERROR: i32 = 1;
+const SETTINGS_PARSE_ERROR: i32 = 2;
+const SETTINGS_UNSUPPORTED_VERSION: i32 = 3;
+const SETTINGS_INVALID_VALUE: i32 = 4;
 
 #[repr(C)]
 pub struct VitypeTransformResult {
@@ -14,6 +24,66 @@ pub struct VitypeTransformResult {
     pub text: *mut c_char,
 }
 
+/// Heap-allocated byte buffer; free with `vitype_bytes_free`.
+#[repr(C)]
+pub struct VitypeBytes {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+fn output_encoding_from_i32(encoding: i32) -> OutputEncoding {
+    match encoding {
+        1 => OutputEncoding::CompositeUnicode,
+        2 => OutputEncoding::Tcvn3,
+        3 => OutputEncoding::Viscii,
+        4 => OutputEncoding::VniWindows,
+        5 => OutputEncoding::Viqr,
+        6 => OutputEncoding::HtmlEntity,
+        7 => OutputEncoding::UnicodeEscape,
+        8 => OutputEncoding::Cp1258,
+        _ => OutputEncoding::Unicode,
+    }
+}
+
+/// Borrows `len` bytes at `data`; a null `data` reads as empty.
+unsafe fn input_bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if data.is_null() {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, len)
+    }
+}
+
+/// Applies `transform` to a UTF-8 C string. Returns null for a null or invalid input; free the
+/// result with `vitype_engine_free_string`.
+fn transform_c_string(input_utf8: *const c_char, transform: fn(&str) -> String) -> *mut c_char {
+    if input_utf8.is_null() {
+        return ptr::null_mut();
+    }
+    let input = unsafe { CStr::from_ptr(input_utf8) };
+    match input.to_str() {
+        Ok(text) => CString::new(transform(text))
+            .map(CString::into_raw)
+            .unwrap_or(ptr::null_mut()),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// `kind`: 0 = exact word, 1 = prefix.
+fn exclusion_from_c(
+    pattern_utf8: *const c_char,
+    kind: i32,
+    case_sensitive: bool,
+) -> Option<ExclusionPattern> {
+    let text = str_from_c(pattern_utf8)?;
+    let pattern = match kind {
+        0 => ExclusionPattern::exact(text),
+        1 => ExclusionPattern::prefix(text),
+        _ => return None,
+    };
+    Some(pattern.case_sensitive(case_sensitive))
+}
+
 fn empty_result() -> VitypeTransformResult {
     VitypeTransformResult {
         has_action: false,
@@ -22,13 +92,24 @@ fn empty_result() -> VitypeTransformResult {
     }
 }
 
-fn convert_to_output_encoding(text: String, encoding: OutputEncoding) -> String {
-    match encoding {
-        OutputEncoding::Unicode => text,
-        OutputEncoding::CompositeUnicode => text.nfd().collect(),
+fn settings_status(result: Result<(), SettingsError>) -> i32 {
+    match result {
+        Ok(()) => SETTINGS_OK,
+        Err(SettingsError::Io(_)) => SETTINGS_IO_ERROR,
+        Err(SettingsError::Parse(_)) | Err(SettingsError::MissingVersion) => SETTINGS_PARSE_ERROR,
+        Err(SettingsError::UnsupportedVersion(_)) => SETTINGS_UNSUPPORTED_VERSION,
+        Err(SettingsError::InvalidValue { .. }) => SETTINGS_INVALID_VALUE,
     }
 }
 
+/// Borrows a NUL-terminated UTF-8 string; `None` for null or invalid UTF-8.
+fn str_from_c<'a>(text_utf8: *const c_char) -> Option<&'a str> {
+    if text_utf8.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(text_utf8) }.to_str().ok()
+}
+
 #[no_mangle]
 pub extern "C" fn vitype_engine_new() -> *mut VitypeEngine {
     Box::into_raw(Box::new(VitypeEngine::new()))
@@ -86,6 +167,85 @@ pub extern "C" fn vitype_engine_set_free_tone_placement(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_literal_foreign_initials(
+    engine: *mut VitypeEngine,
+    enabled: bool,
+) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_literal_foreign_initials(enabled);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_telex_bracket_shortcuts(
+    engine: *mut VitypeEngine,
+    enabled: bool,
+) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_telex_bracket_shortcuts(enabled);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_quick_telex(engine: *mut VitypeEngine, enabled: bool) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_quick_telex(enabled);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_quick_consonants(engine: *mut VitypeEngine, enabled: bool) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_quick_consonants(enabled);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_check_spelling(engine: *mut VitypeEngine, enabled: bool) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_check_spelling(enabled);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_restore_invalid_words(
+    engine: *mut VitypeEngine,
+    enabled: bool,
+) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_restore_invalid_words(enabled);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_english_bypass(engine: *mut VitypeEngine, enabled: bool) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_english_bypass(enabled);
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn vitype_engine_set_input_method(engine: *mut VitypeEngine, method: i32) {
     if engine.is_null() {
@@ -94,6 +254,9 @@ pub extern "C" fn vitype_engine_set_input_method(engine: *mut VitypeEngine, meth
     unsafe {
         let input_method = match method {
             1 => InputMethod::Vni,
+            2 => InputMethod::Viqr,
+            3 => InputMethod::SimpleTelex,
+            4 => InputMethod::MicrosoftVietnamese,
             _ => InputMethod::Telex,
         };
         (*engine).set_input_method(input_method);
@@ -106,11 +269,23 @@ pub extern "C" fn vitype_engine_set_output_encoding(engine: *mut VitypeEngine, e
         return;
     }
     unsafe {
-        let output_encoding = match encoding {
-            1 => OutputEncoding::CompositeUnicode,
-            _ => OutputEncoding::Unicode,
+        (*engine).set_output_encoding(output_encoding_from_i32(encoding));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_set_delete_count_unit(engine: *mut VitypeEngine, unit: i32) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        let delete_count_unit = match unit {
+            1 => DeleteCountUnit::Utf16CodeUnits,
+            2 => DeleteCountUnit::Utf8Bytes,
+            3 => DeleteCountUnit::GraphemeClusters,
+            _ => DeleteCountUnit::CodePoints,
         };
-        (*engine).set_output_encoding(output_encoding);
+        (*engine).set_delete_count_unit(delete_count_unit);
     }
 }
 
@@ -128,6 +303,139 @@ pub extern "C" fn vitype_engine_set_tone_placement(engine: *mut VitypeEngine, pl
     }
 }
 
+#[no_mangle]
+pub extern "C" fn vitype_engine_load_settings(
+    engine: *mut VitypeEngine,
+    path_utf8: *const c_char,
+) -> i32 {
+    if engine.is_null() {
+        return SETTINGS_INVALID_ARGUMENT;
+    }
+    let Some(path) = str_from_c(path_utf8) else {
+        return SETTINGS_INVALID_ARGUMENT;
+    };
+    let result = EngineConfig::load(path).map(|config| unsafe { (*engine).apply_config(config) });
+    settings_status(result)
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_load_keymap(
+    engine: *mut VitypeEngine,
+    path_utf8: *const c_char,
+) -> i32 {
+    if engine.is_null() {
+        return SETTINGS_INVALID_ARGUMENT;
+    }
+    let Some(path) = str_from_c(path_utf8) else {
+        return SETTINGS_INVALID_ARGUMENT;
+    };
+    let result = Keymap::load(path)
+        .map(|keymap| unsafe { (*engine).set_input_method(InputMethod::Custom(Arc::new(keymap))) });
+    settings_status(result)
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_load_english_words(
+    engine: *mut VitypeEngine,
+    path_utf8: *const c_char,
+) -> i32 {
+    if engine.is_null() {
+        return SETTINGS_INVALID_ARGUMENT;
+    }
+    let Some(path) = str_from_c(path_utf8) else {
+        return SETTINGS_INVALID_ARGUMENT;
+    };
+    let result = unsafe { (*engine).load_english_words(path) };
+    settings_status(result)
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_add_exclusion(
+    engine: *mut VitypeEngine,
+    pattern_utf8: *const c_char,
+    kind: i32,
+    case_sensitive: bool,
+) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+    let Some(pattern) = exclusion_from_c(pattern_utf8, kind, case_sensitive) else {
+        return false;
+    };
+    unsafe { (*engine).add_exclusion(pattern) }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_remove_exclusion(
+    engine: *mut VitypeEngine,
+    pattern_utf8: *const c_char,
+    kind: i32,
+    case_sensitive: bool,
+) -> bool {
+    if engine.is_null() {
+        return false;
+    }
+    let Some(pattern) = exclusion_from_c(pattern_utf8, kind, case_sensitive) else {
+        return false;
+    };
+    unsafe { (*engine).remove_exclusion(&pattern) }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_clear_exclusions(engine: *mut VitypeEngine) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe {
+        (*engine).set_exclusions(Default::default());
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_load_exclusions(
+    engine: *mut VitypeEngine,
+    path_utf8: *const c_char,
+) -> i32 {
+    if engine.is_null() {
+        return SETTINGS_INVALID_ARGUMENT;
+    }
+    let Some(path) = str_from_c(path_utf8) else {
+        return SETTINGS_INVALID_ARGUMENT;
+    };
+    let result = unsafe { (*engine).load_exclusions(path) };
+    settings_status(result)
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_save_exclusions(
+    engine: *const VitypeEngine,
+    path_utf8: *const c_char,
+) -> i32 {
+    if engine.is_null() {
+        return SETTINGS_INVALID_ARGUMENT;
+    }
+    let Some(path) = str_from_c(path_utf8) else {
+        return SETTINGS_INVALID_ARGUMENT;
+    };
+    let result = unsafe { (*engine).save_exclusions(path) };
+    settings_status(result)
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_engine_save_settings(
+    engine: *const VitypeEngine,
+    path_utf8: *const c_char,
+) -> i32 {
+    if engine.is_null() {
+        return SETTINGS_INVALID_ARGUMENT;
+    }
+    let Some(path) = str_from_c(path_utf8) else {
+        return SETTINGS_INVALID_ARGUMENT;
+    };
+    let config = unsafe { (*engine).config() };
+    settings_status(config.save(path))
+}
+
 #[no_mangle]
 pub extern "C" fn vitype_engine_process(
     engine: *mut VitypeEngine,
@@ -143,12 +451,10 @@ pub extern "C" fn vitype_engine_process(
         Err(_) => return empty_result(),
     };
 
-    let (action, output_encoding) =
-        unsafe { ((*engine).process(input_str), (*engine).output_encoding()) };
+    let action = unsafe { (*engine).process_encoded(input_str) };
     match action {
         Some(action) => {
-            let output_text = convert_to_output_encoding(action.text, output_encoding);
-            let c_text = CString::new(output_text).unwrap_or_else(|_| CString::new("").unwrap());
+            let c_text = CString::new(action.text).unwrap_or_else(|_| CString::new("").unwrap());
             VitypeTransformResult {
                 has_action: true,
                 delete_count: action.delete_count as i32,
@@ -168,3 +474,57 @@ pub extern "C" fn vitype_engine_free_string(text: *mut c_char) {
         drop(CString::from_raw(text));
     }
 }
+
+#[no_mangle]
+pub extern "C" fn vitype_convert_text(
+    input: *const u8,
+    len: usize,
+    from: i32,
+    to: i32,
+) -> VitypeBytes {
+    let input = unsafe { input_bytes(input, len) };
+    let output = convert_text(
+        input,
+        output_encoding_from_i32(from),
+        output_encoding_from_i32(to),
+    )
+    .into_boxed_slice();
+    let len = output.len();
+    VitypeBytes {
+        data: Box::into_raw(output) as *mut u8,
+        len,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_detect_encoding(input: *const u8, len: usize) -> i32 {
+    let input = unsafe { input_bytes(input, len) };
+    detect_encoding(input) as i32
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_bytes_free(bytes: VitypeBytes) {
+    if bytes.data.is_null() {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            bytes.data, bytes.len,
+        )));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_remove_tones(input_utf8: *const c_char) -> *mut c_char {
+    transform_c_string(input_utf8, remove_tones)
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_remove_diacritics(input_utf8: *const c_char) -> *mut c_char {
+    transform_c_string(input_utf8, remove_diacritics)
+}
+
+#[no_mangle]
+pub extern "C" fn vitype_slugify(input_utf8: *const c_char) -> *mut c_char {
+    transform_c_string(input_utf8, slugify)
+}
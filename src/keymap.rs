@@ -0,0 +1,614 @@
+//! User-defined input methods.
+//!
+//! A [`Keymap`] maps keys to the canonical editing actions shared by every built-in method
+//! (tones, shapes, đ) and says which keys end a word. Load one from a file and select it with
+//! `InputMethod::Custom`:
+//!
+//! ```toml
+//! version = 1
+//! name = "VNI (custom)"
+//! double_key_escape = true          # repeating a key undoes it ("a66" → "a6")
+//! literal_prefix = "\\"             # optional: the next key is typed literally
+//!
+//! [keys]                            # "tone_s" | "tone_f" | "tone_r" | "tone_x" | "tone_j"
+//! "1" = "tone_s"                    # "remove_tone" | "circumflex" | "horn" | "breve"
+//! "6" = "circumflex"                # "d_stroke" | "literal"
+//! "9" = "d_stroke"
+//!
+//! [boundaries]
+//! punctuation = true                # ASCII punctuation ends a word
+//! digits = false                    # ASCII digits end a word
+//! extra = ""                        # more characters that end a word
+//! ```
+//!
+//! See `CUSTOM_KEYMAPS.md` for how each action behaves.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{lower_char, InputMethod, KeyTransformAction, WTransformKind};
+use crate::diacritics::{apply_shape_preserving_tone, escape_shape_preserving_tone, VowelShape};
+use crate::settings::{is_json_path, SettingsError, SETTINGS_VERSION};
+use crate::viqr::VIQR_KEYMAP;
+use crate::vni::VNI_KEYMAP;
+use crate::VitypeEngine;
+
+// ==================== Keymap ====================
+
+/// One of the five Vietnamese tone marks.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToneMark {
+    /// Sắc (Telex `s`).
+    Acute,
+    /// Huyền (Telex `f`).
+    Grave,
+    /// Hỏi (Telex `r`).
+    HookAbove,
+    /// Ngã (Telex `x`).
+    Tilde,
+    /// Nặng (Telex `j`).
+    DotBelow,
+}
+
+impl ToneMark {
+    /// Tone for an internal (Telex) tone key.
+    pub(crate) fn from_tone_key(key: char) -> Option<Self> {
+        Some(match key {
+            's' => ToneMark::Acute,
+            'f' => ToneMark::Grave,
+            'r' => ToneMark::HookAbove,
+            'x' => ToneMark::Tilde,
+            'j' => ToneMark::DotBelow,
+            _ => return None,
+        })
+    }
+}
+
+/// What a key does in a [`Keymap`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    Tone(ToneMark),
+    RemoveTone,
+    /// a → â, e → ê, o → ô.
+    Circumflex,
+    /// o → ơ, u → ư, including the compound ươ rules.
+    Horn,
+    /// a → ă.
+    Breve,
+    /// d → đ.
+    DStroke,
+    /// Always typed as-is, even if the key would otherwise end a word.
+    Literal,
+}
+
+/// A declarative input method, used through `InputMethod::Custom`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Keymap {
+    name: String,
+    keys: BTreeMap<char, KeyAction>,
+    punctuation_boundaries: bool,
+    digit_boundaries: bool,
+    extra_boundaries: BTreeSet<char>,
+    double_key_escape: bool,
+    literal_prefix: Option<char>,
+}
+
+impl Keymap {
+    pub fn builder(name: impl Into<String>) -> KeymapBuilder {
+        KeymapBuilder {
+            keymap: Keymap {
+                name: name.into(),
+                keys: BTreeMap::new(),
+                punctuation_boundaries: true,
+                digit_boundaries: true,
+                extra_boundaries: BTreeSet::new(),
+                double_key_escape: true,
+                literal_prefix: None,
+            },
+        }
+    }
+
+    /// The keymap behind [`InputMethod::Vni`], a starting point for variants of it.
+    pub fn vni() -> Self {
+        VNI_KEYMAP.clone()
+    }
+
+    /// The keymap behind [`InputMethod::Viqr`], a starting point for variants of it.
+    pub fn viqr() -> Self {
+        VIQR_KEYMAP.clone()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Action bound to `key`. Keys are matched exactly first, then by their lowercase form.
+    pub fn action_for(&self, key: char) -> Option<KeyAction> {
+        self.keys
+            .get(&key)
+            .or_else(|| self.keys.get(&lower_char(key)))
+            .copied()
+    }
+
+    /// First key bound to `action`, if any.
+    pub(crate) fn key_for(&self, action: KeyAction) -> Option<char> {
+        self.keys
+            .iter()
+            .find(|(_, bound)| **bound == action)
+            .map(|(key, _)| *key)
+    }
+
+    /// Whether `key` ends the current word. Keys bound to [`KeyAction::Literal`] never do.
+    pub fn is_word_boundary(&self, key: char) -> bool {
+        if self.action_for(key) == Some(KeyAction::Literal) {
+            return false;
+        }
+        key.is_ascii_whitespace()
+            || (self.punctuation_boundaries && key.is_ascii_punctuation())
+            || (self.digit_boundaries && key.is_ascii_digit())
+            || self.extra_boundaries.contains(&key)
+    }
+
+    pub fn double_key_escape(&self) -> bool {
+        self.double_key_escape
+    }
+
+    pub fn literal_prefix(&self) -> Option<char> {
+        self.literal_prefix
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, SettingsError> {
+        let file: KeymapFile =
+            toml::from_str(text).map_err(|err| SettingsError::Parse(err.to_string()))?;
+        file.into_keymap(true)
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(&KeymapFile::from_keymap(self, true))
+            .expect("keymaps always serialize to TOML")
+    }
+
+    pub fn from_json_str(text: &str) -> Result<Self, SettingsError> {
+        let file: KeymapFile =
+            serde_json::from_str(text).map_err(|err| SettingsError::Parse(err.to_string()))?;
+        file.into_keymap(true)
+    }
+
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(&KeymapFile::from_keymap(self, true))
+            .expect("keymaps always serialize to JSON")
+    }
+
+    /// Reads a keymap file. Files ending in `.json` are parsed as JSON, anything else as TOML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        if is_json_path(path) {
+            Self::from_json_str(&text)
+        } else {
+            Self::from_toml_str(&text)
+        }
+    }
+}
+
+/// Builder for [`Keymap`]. Punctuation and digits end words and double-key escape is on
+/// unless changed.
+#[derive(Clone, Debug)]
+pub struct KeymapBuilder {
+    keymap: Keymap,
+}
+
+impl KeymapBuilder {
+    pub fn key(mut self, key: char, action: KeyAction) -> Self {
+        self.keymap.keys.insert(key, action);
+        self
+    }
+
+    pub fn punctuation_boundaries(mut self, enabled: bool) -> Self {
+        self.keymap.punctuation_boundaries = enabled;
+        self
+    }
+
+    pub fn digit_boundaries(mut self, enabled: bool) -> Self {
+        self.keymap.digit_boundaries = enabled;
+        self
+    }
+
+    pub fn extra_boundaries(mut self, keys: &str) -> Self {
+        self.keymap.extra_boundaries.extend(keys.chars());
+        self
+    }
+
+    pub fn double_key_escape(mut self, enabled: bool) -> Self {
+        self.keymap.double_key_escape = enabled;
+        self
+    }
+
+    pub fn literal_prefix(mut self, key: Option<char>) -> Self {
+        self.keymap.literal_prefix = key;
+        self
+    }
+
+    pub fn build(self) -> Keymap {
+        self.keymap
+    }
+}
+
+// ==================== Serialized Form ====================
+
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct KeymapFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<u32>,
+    name: Option<String>,
+    double_key_escape: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    literal_prefix: Option<String>,
+    #[serde(default)]
+    keys: BTreeMap<String, String>,
+    #[serde(default)]
+    boundaries: BoundariesFile,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct BoundariesFile {
+    punctuation: Option<bool>,
+    digits: Option<bool>,
+    extra: Option<String>,
+}
+
+impl KeymapFile {
+    /// `versioned` is false when the keymap is embedded in a settings file, which carries
+    /// its own version.
+    pub(crate) fn from_keymap(keymap: &Keymap, versioned: bool) -> Self {
+        Self {
+            version: versioned.then_some(SETTINGS_VERSION),
+            name: Some(keymap.name.clone()),
+            double_key_escape: Some(keymap.double_key_escape),
+            literal_prefix: keymap.literal_prefix.map(String::from),
+            keys: keymap
+                .keys
+                .iter()
+                .map(|(key, action)| (key.to_string(), key_action_name(*action).to_string()))
+                .collect(),
+            boundaries: BoundariesFile {
+                punctuation: Some(keymap.punctuation_boundaries),
+                digits: Some(keymap.digit_boundaries),
+                extra: Some(keymap.extra_boundaries.iter().collect()),
+            },
+        }
+    }
+
+    pub(crate) fn into_keymap(self, versioned: bool) -> Result<Keymap, SettingsError> {
+        if versioned {
+            let version = self.version.ok_or(SettingsError::MissingVersion)?;
+            if version > SETTINGS_VERSION {
+                return Err(SettingsError::UnsupportedVersion(version));
+            }
+        }
+
+        let mut builder = Keymap::builder(self.name.unwrap_or_default());
+        for (key, action) in self.keys {
+            let key = single_char(&key).ok_or(SettingsError::InvalidValue {
+                key: "keys",
+                value: key,
+            })?;
+            let action = parse_key_action(&action).ok_or(SettingsError::InvalidValue {
+                key: "keys",
+                value: action,
+            })?;
+            builder = builder.key(key, action);
+        }
+        if let Some(value) = self.double_key_escape {
+            builder = builder.double_key_escape(value);
+        }
+        if let Some(value) = self.literal_prefix {
+            let key = single_char(&value).ok_or(SettingsError::InvalidValue {
+                key: "literal_prefix",
+                value,
+            })?;
+            builder = builder.literal_prefix(Some(key));
+        }
+        if let Some(value) = self.boundaries.punctuation {
+            builder = builder.punctuation_boundaries(value);
+        }
+        if let Some(value) = self.boundaries.digits {
+            builder = builder.digit_boundaries(value);
+        }
+        if let Some(value) = self.boundaries.extra {
+            builder = builder.extra_boundaries(&value);
+        }
+        Ok(builder.build())
+    }
+}
+
+fn single_char(value: &str) -> Option<char> {
+    let mut chars = value.chars();
+    let ch = chars.next()?;
+    chars.next().is_none().then_some(ch)
+}
+
+fn key_action_name(action: KeyAction) -> &'static str {
+    match action {
+        KeyAction::Tone(ToneMark::Acute) => "tone_s",
+        KeyAction::Tone(ToneMark::Grave) => "tone_f",
+        KeyAction::Tone(ToneMark::HookAbove) => "tone_r",
+        KeyAction::Tone(ToneMark::Tilde) => "tone_x",
+        KeyAction::Tone(ToneMark::DotBelow) => "tone_j",
+        KeyAction::RemoveTone => "remove_tone",
+        KeyAction::Circumflex => "circumflex",
+        KeyAction::Horn => "horn",
+        KeyAction::Breve => "breve",
+        KeyAction::DStroke => "d_stroke",
+        KeyAction::Literal => "literal",
+    }
+}
+
+fn parse_key_action(value: &str) -> Option<KeyAction> {
+    Some(match value {
+        "tone_s" => KeyAction::Tone(ToneMark::Acute),
+        "tone_f" => KeyAction::Tone(ToneMark::Grave),
+        "tone_r" => KeyAction::Tone(ToneMark::HookAbove),
+        "tone_x" => KeyAction::Tone(ToneMark::Tilde),
+        "tone_j" => KeyAction::Tone(ToneMark::DotBelow),
+        "remove_tone" => KeyAction::RemoveTone,
+        "circumflex" => KeyAction::Circumflex,
+        "horn" => KeyAction::Horn,
+        "breve" => KeyAction::Breve,
+        "d_stroke" => KeyAction::DStroke,
+        "literal" => KeyAction::Literal,
+        _ => return None,
+    })
+}
+
+// ==================== Keymap Helper Functions ====================
+
+pub(crate) fn shape_for_action(action: KeyAction) -> Option<VowelShape> {
+    Some(match action {
+        KeyAction::Circumflex => VowelShape::Circumflex,
+        KeyAction::Horn => VowelShape::Horn,
+        KeyAction::Breve => VowelShape::Breve,
+        _ => return None,
+    })
+}
+
+/// Internal (Telex) tone key for a tone action.
+pub(crate) fn internal_tone_key(action: KeyAction) -> Option<char> {
+    Some(match action {
+        KeyAction::Tone(ToneMark::Acute) => 's',
+        KeyAction::Tone(ToneMark::Grave) => 'f',
+        KeyAction::Tone(ToneMark::HookAbove) => 'r',
+        KeyAction::Tone(ToneMark::Tilde) => 'x',
+        KeyAction::Tone(ToneMark::DotBelow) => 'j',
+        KeyAction::RemoveTone => 'z',
+        _ => return None,
+    })
+}
+
+// ==================== Keymap Methods on VitypeEngine ====================
+
+impl VitypeEngine {
+    /// Keymap of the active input method: the built-in VNI or VIQR keymap, or the custom one.
+    fn active_keymap(&self) -> Option<&Keymap> {
+        match &self.config.input_method {
+            InputMethod::Vni => Some(&VNI_KEYMAP),
+            InputMethod::Viqr => Some(&VIQR_KEYMAP),
+            InputMethod::Custom(keymap) => Some(keymap),
+            InputMethod::Telex | InputMethod::SimpleTelex | InputMethod::MicrosoftVietnamese => {
+                None
+            }
+        }
+    }
+
+    fn keymap_key_action(&self, ch: char) -> Option<KeyAction> {
+        self.active_keymap()?.action_for(ch)
+    }
+
+    /// Mapped punctuation key that acts as a diacritic inside a word (VIQR `'`, `^`, `-`).
+    pub(super) fn is_keymap_modifier_key(&self, ch: char) -> bool {
+        let Some(keymap) = self.active_keymap() else {
+            return false;
+        };
+        !matches!(keymap.action_for(ch), None | Some(KeyAction::Literal))
+            && keymap.is_word_boundary(ch)
+    }
+
+    pub(super) fn is_keymap_literal_prefix_key(&self, ch: char) -> bool {
+        self.active_keymap()
+            .is_some_and(|keymap| keymap.literal_prefix() == Some(ch))
+    }
+
+    pub(super) fn try_keymap_escape_sequence(&mut self, ch: char) -> Option<KeyTransformAction> {
+        if !self.active_keymap()?.double_key_escape() {
+            return None;
+        }
+        let last_key = self.last_transform_key?;
+        if lower_char(ch) != lower_char(last_key) {
+            return None;
+        }
+        let action = self.keymap_key_action(ch)?;
+
+        // Handle horn escapes for compound transforms (similar to 'w' in Telex)
+        if action == KeyAction::Horn {
+            if let Some(action) = self.try_escape_compound_horn_key(ch, ch) {
+                return Some(action);
+            }
+        }
+
+        // Handle đ escapes: "d99" → "d9", "ddd" → "dd"
+        if action == KeyAction::DStroke {
+            if let Some(&last_char) = self.buffer.last() {
+                if last_char == 'đ' || last_char == 'Đ' {
+                    let d_char = if last_char == 'Đ' { 'D' } else { 'd' };
+                    self.buffer.pop();
+                    self.buffer.push(d_char);
+                    self.buffer.push(ch);
+                    self.clear_last_transform_and_suppress(ch);
+                    return Some(KeyTransformAction {
+                        delete_count: 1,
+                        text: format!("{}{}", d_char, ch),
+                    });
+                }
+            }
+        }
+
+        // Handle shape escapes
+        if let Some(shape) = shape_for_action(action) {
+            if let Some(&last_char) = self.buffer.last() {
+                if let Some(original) = escape_shape_preserving_tone(last_char, shape) {
+                    self.buffer.pop();
+                    self.buffer.push(original);
+                    self.buffer.push(ch);
+                    self.clear_last_transform_and_suppress(ch);
+                    return Some(KeyTransformAction {
+                        delete_count: 1,
+                        text: format!("{}{}", original, ch),
+                    });
+                }
+            }
+
+            if let Some((index, original)) =
+                self.find_last_keymap_untransformable_vowel(shape, self.buffer.len())
+            {
+                let delete_count = self.buffer.len() - index;
+                self.buffer[index] = original;
+                self.buffer.push(ch);
+                self.clear_last_transform_and_suppress(ch);
+                let output_text = self.buffer_string_from(index);
+                return Some(KeyTransformAction {
+                    delete_count,
+                    text: output_text,
+                });
+            }
+        }
+
+        // Handle tone escapes
+        if let Some(internal_tone_key) = internal_tone_key(action) {
+            if let Some(action) = self.try_escape_repeated_tone_key(ch, internal_tone_key, ch) {
+                return Some(action);
+            }
+        }
+
+        None
+    }
+
+    pub(super) fn try_keymap_consonant_transform(
+        &mut self,
+        ch: char,
+    ) -> Option<KeyTransformAction> {
+        if self.keymap_key_action(ch)? != KeyAction::DStroke {
+            return None;
+        }
+        self.try_d_stroke(ch)
+    }
+
+    pub(super) fn try_keymap_vowel_transform(&mut self, ch: char) -> Option<KeyTransformAction> {
+        let action = self.keymap_key_action(ch)?;
+        let shape = shape_for_action(action)?;
+
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        // Handle compound transforms for horn keys (similar to 'w' in Telex)
+        if action == KeyAction::Horn {
+            if let Some(action) = self.try_compound_ua_escape(ch) {
+                return Some(action);
+            }
+
+            if let Some(action) = self.try_compound_uo_final_consonant_transform(ch) {
+                return Some(action);
+            }
+
+            if let Some(action) = self.try_compound_uoi_transform(ch) {
+                return Some(action);
+            }
+
+            if let Some(action) = self.try_compound_uu_transform(ch) {
+                return Some(action);
+            }
+
+            if let Some(action) = self.try_compound_uou_transform(ch) {
+                return Some(action);
+            }
+
+            if let Some(action) = self.try_compound_ou_transform(ch) {
+                return Some(action);
+            }
+
+            if let Some(action) = self.try_compound_uow_transform(ch) {
+                return Some(action);
+            }
+
+            if let Some(action) = self.try_compound_uaw_transform(ch) {
+                return Some(action);
+            }
+        }
+
+        let trigger_index = self.buffer.len() - 1;
+        let vowel_index = self.find_last_keymap_transformable_vowel(shape, trigger_index, 4)?;
+        let vowel = self.buffer[vowel_index];
+
+        let result = apply_shape_preserving_tone(vowel, shape)?;
+
+        let delete_count = trigger_index - vowel_index;
+        self.buffer[vowel_index] = result;
+        self.buffer.pop(); // Remove the transform key
+        self.last_transform_key = Some(ch);
+        self.last_w_transform_kind = WTransformKind::None;
+
+        if self.config.auto_fix_tone {
+            if let Some(action) = self.reposition_tone_if_needed(false, Some(vowel_index)) {
+                return Some(action);
+            }
+        }
+
+        let output_text = self.buffer_string_from(vowel_index);
+        Some(KeyTransformAction {
+            delete_count,
+            text: output_text,
+        })
+    }
+
+    pub(super) fn try_keymap_tone_mark(&mut self, ch: char) -> Option<KeyTransformAction> {
+        let internal_tone_key = internal_tone_key(self.keymap_key_action(ch)?)?;
+        self.apply_tone_mark_internal(internal_tone_key, ch)
+    }
+
+    // ==================== Keymap Helper Methods ====================
+
+    fn find_last_keymap_transformable_vowel(
+        &self,
+        shape: VowelShape,
+        before: usize,
+        max_distance: usize,
+    ) -> Option<usize> {
+        let allow_adjacent_skip = |_base_lower: char| true;
+        self.find_last_vowel_index_with_predicate(
+            before,
+            max_distance,
+            allow_adjacent_skip,
+            |ch, _| apply_shape_preserving_tone(ch, shape).is_some(),
+        )
+    }
+
+    fn find_last_keymap_untransformable_vowel(
+        &self,
+        shape: VowelShape,
+        before: usize,
+    ) -> Option<(usize, char)> {
+        let mut index = before;
+        while index > 0 {
+            index -= 1;
+            if let Some(original) = escape_shape_preserving_tone(self.buffer[index], shape) {
+                return Some((index, original));
+            }
+        }
+        None
+    }
+}
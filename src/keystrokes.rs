@@ -0,0 +1,226 @@
+//! The inverse of the engine: Vietnamese text in, keystrokes out.
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::common::{lower_char, InputMethod};
+use crate::config::EngineConfig;
+use crate::diacritics::{escape_shape_preserving_tone, split_vowel_and_tone, VowelShape};
+use crate::keymap::{KeyAction, ToneMark};
+use crate::transliterate::transliterate;
+use crate::viqr::VIQR_LITERAL_PREFIX;
+
+/// Returns keystrokes that type `text` with `input_method`, e.g. `nguwowif Vieetj` (Telex) or
+/// `ngu7o7i2 Vie6t5` (VNI) for `người Việt`.
+///
+/// Shape keys follow their letter and the tone key ends the word, the way most people type.
+/// Words that come out differently that way (English words, digits after a VNI word, ...) are
+/// spelled key by key with the input method's escapes instead. The result is checked: when it
+/// is `Some`, [`transliterate`] with the default [`EngineConfig`] turns it back into `text`
+/// (in precomposed form). It is `None` if `text` cannot be typed with `input_method`, e.g. a
+/// custom keymap without a horn key and a word with `ư`.
+///
+/// ```
+/// use vitype_core::{to_keystrokes, InputMethod};
+///
+/// assert_eq!(
+///     to_keystrokes("người Việt", InputMethod::Telex).as_deref(),
+///     Some("nguwowif Vieetj")
+/// );
+/// assert_eq!(
+///     to_keystrokes("người Việt", InputMethod::Vni).as_deref(),
+///     Some("ngu7o7i2 Vie6t5")
+/// );
+/// ```
+pub fn to_keystrokes(text: &str, input_method: InputMethod) -> Option<String> {
+    let text: String = text.nfc().collect();
+    let mut keys = String::with_capacity(text.len() * 2);
+    // Whitespace ends a word in every input method, so each chunk can be spelled on its own.
+    for chunk in text.split_inclusive(char::is_whitespace) {
+        keys.push_str(&chunk_keystrokes(chunk, &input_method)?);
+    }
+    (transliterate(&keys, input_method, EngineConfig::default()) == text).then_some(keys)
+}
+
+/// Keys for one character: the letter with its shape, and its tone key if it has a tone.
+struct CharKeys {
+    keys: String,
+    tone: Option<char>,
+}
+
+impl CharKeys {
+    fn inline(&self) -> String {
+        let mut keys = self.keys.clone();
+        keys.extend(self.tone);
+        keys
+    }
+}
+
+fn chunk_keystrokes(chunk: &str, input_method: &InputMethod) -> Option<String> {
+    let typed = |keys: &str| transliterate(keys, input_method.clone(), EngineConfig::default());
+    let chars = chunk
+        .chars()
+        .map(|ch| Some((ch, char_keys(ch, input_method)?)))
+        .collect::<Option<Vec<_>>>()?;
+
+    for tone_at_word_end in [true, false] {
+        let keys = canonical_keystrokes(&chars, tone_at_word_end);
+        if typed(&keys) == chunk {
+            return Some(keys);
+        }
+    }
+
+    // Spell the chunk one character at a time, escaping keys the engine would otherwise
+    // transform.
+    let literal_prefix = literal_prefix(input_method);
+    let mut keys = String::new();
+    let mut expected = String::new();
+    for (ch, char_keys) in &chars {
+        expected.push(*ch);
+        let inline = char_keys.inline();
+        let doubled = inline.chars().last().map(|last| format!("{inline}{last}"));
+        let prefixed = literal_prefix.map(|prefix| format!("{prefix}{inline}"));
+        let spelled = [Some(inline), doubled, prefixed]
+            .into_iter()
+            .flatten()
+            .find(|candidate| typed(&format!("{keys}{candidate}")) == expected)?;
+        keys.push_str(&spelled);
+    }
+    Some(keys)
+}
+
+/// Joins the keys of `chars`, moving the tone key of each word to the end of the word when
+/// `tone_at_word_end` is set and the word has a single tone.
+fn canonical_keystrokes(chars: &[(char, CharKeys)], tone_at_word_end: bool) -> String {
+    let mut keys = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let word_len = chars[index..]
+            .iter()
+            .take_while(|(ch, _)| ch.is_alphabetic())
+            .count()
+            .max(1);
+        let word = &chars[index..index + word_len];
+        let mut tones = word.iter().filter_map(|(_, char_keys)| char_keys.tone);
+        let word_tone = tones.next().filter(|_| tones.next().is_none());
+
+        if tone_at_word_end && word_tone.is_some() {
+            keys.extend(word.iter().map(|(_, char_keys)| char_keys.keys.as_str()));
+            keys.extend(word_tone);
+        } else {
+            keys.extend(word.iter().map(|(_, char_keys)| char_keys.inline()));
+        }
+        index += word_len;
+    }
+    keys
+}
+
+fn char_keys(ch: char, input_method: &InputMethod) -> Option<CharKeys> {
+    let (letter, tone) = split_vowel_and_tone(ch);
+    let tone = match tone {
+        Some(tone) => {
+            let action = KeyAction::Tone(ToneMark::from_tone_key(tone)?);
+            Some(action_key(input_method, action, letter)?)
+        }
+        None => None,
+    };
+
+    let mut keys = String::new();
+    if matches!(input_method, InputMethod::MicrosoftVietnamese) {
+        // Shaped letters and đ have keys of their own on the Windows layout.
+        keys.push(letter);
+    } else if let Some(base) = d_stroke_base(letter) {
+        keys.push(base);
+        keys.push(action_key(input_method, KeyAction::DStroke, base)?);
+    } else if let Some((base, shape)) = unshape(letter) {
+        keys.push(base);
+        keys.push(action_key(input_method, shape_action(shape), base)?);
+    } else {
+        keys.push(letter);
+    }
+    Some(CharKeys { keys, tone })
+}
+
+fn d_stroke_base(letter: char) -> Option<char> {
+    match letter {
+        'đ' => Some('d'),
+        'Đ' => Some('D'),
+        _ => None,
+    }
+}
+
+/// Splits a shaped vowel (ă â ê ô ơ ư) into its plain vowel and shape.
+fn unshape(letter: char) -> Option<(char, VowelShape)> {
+    [VowelShape::Circumflex, VowelShape::Horn, VowelShape::Breve]
+        .into_iter()
+        .find_map(|shape| Some((escape_shape_preserving_tone(letter, shape)?, shape)))
+}
+
+fn shape_action(shape: VowelShape) -> KeyAction {
+    match shape {
+        VowelShape::Circumflex => KeyAction::Circumflex,
+        VowelShape::Horn => KeyAction::Horn,
+        VowelShape::Breve => KeyAction::Breve,
+    }
+}
+
+/// Key that performs `action` on `base` in `input_method`.
+fn action_key(input_method: &InputMethod, action: KeyAction, base: char) -> Option<char> {
+    let key = match input_method {
+        InputMethod::Telex | InputMethod::SimpleTelex => match action {
+            KeyAction::Tone(ToneMark::Acute) => 's',
+            KeyAction::Tone(ToneMark::Grave) => 'f',
+            KeyAction::Tone(ToneMark::HookAbove) => 'r',
+            KeyAction::Tone(ToneMark::Tilde) => 'x',
+            KeyAction::Tone(ToneMark::DotBelow) => 'j',
+            KeyAction::Circumflex => lower_char(base),
+            KeyAction::Horn | KeyAction::Breve => 'w',
+            KeyAction::DStroke => 'd',
+            _ => return None,
+        },
+        InputMethod::Vni => match action {
+            KeyAction::Tone(ToneMark::Acute) => '1',
+            KeyAction::Tone(ToneMark::Grave) => '2',
+            KeyAction::Tone(ToneMark::HookAbove) => '3',
+            KeyAction::Tone(ToneMark::Tilde) => '4',
+            KeyAction::Tone(ToneMark::DotBelow) => '5',
+            KeyAction::Circumflex => '6',
+            KeyAction::Horn => '7',
+            KeyAction::Breve => '8',
+            KeyAction::DStroke => '9',
+            _ => return None,
+        },
+        InputMethod::Viqr => match action {
+            KeyAction::Tone(ToneMark::Acute) => '\'',
+            KeyAction::Tone(ToneMark::Grave) => '`',
+            KeyAction::Tone(ToneMark::HookAbove) => '?',
+            KeyAction::Tone(ToneMark::Tilde) => '~',
+            KeyAction::Tone(ToneMark::DotBelow) => '.',
+            KeyAction::Circumflex => '^',
+            KeyAction::Horn => '+',
+            KeyAction::Breve => '(',
+            KeyAction::DStroke => 'd',
+            _ => return None,
+        },
+        InputMethod::MicrosoftVietnamese => match action {
+            KeyAction::Tone(ToneMark::Acute) => '\u{0301}',
+            KeyAction::Tone(ToneMark::Grave) => '\u{0300}',
+            KeyAction::Tone(ToneMark::HookAbove) => '\u{0309}',
+            KeyAction::Tone(ToneMark::Tilde) => '\u{0303}',
+            KeyAction::Tone(ToneMark::DotBelow) => '\u{0323}',
+            _ => return None,
+        },
+        InputMethod::Custom(keymap) => return keymap.key_for(action),
+    };
+    Some(key)
+}
+
+fn literal_prefix(input_method: &InputMethod) -> Option<char> {
+    match input_method {
+        InputMethod::Viqr => Some(VIQR_LITERAL_PREFIX),
+        InputMethod::Custom(keymap) => keymap.literal_prefix(),
+        InputMethod::Telex
+        | InputMethod::SimpleTelex
+        | InputMethod::Vni
+        | InputMethod::MicrosoftVietnamese => None,
+    }
+}
@@ -1,24 +1,76 @@
+//! Vietnamese input method engine (Telex, VNI, VIQR, the Windows Vietnamese layout and
+//! user-defined keymaps).
+//!
+//! [`VitypeEngine`] consumes one keystroke at a time and answers with an optional
+//! [`KeyTransformAction`] describing how the host should rewrite the text it has
+//! already emitted. The same engine backs the C API declared in `include/vitype_core.h`.
+//!
+//! ```
+//! use vitype_core::{KeyTransformAction, VitypeEngine};
+//!
+//! let mut engine = VitypeEngine::new();
+//! let mut output = String::new();
+//! for key in "vieejt".chars() {
+//!     match engine.process(&key.to_string()) {
+//!         Some(KeyTransformAction { delete_count, text }) => {
+//!             for _ in 0..delete_count {
+//!                 output.pop();
+//!             }
+//!             output.push_str(&text);
+//!         }
+//!         None => output.push(key),
+//!     }
+//! }
+//! assert_eq!(output, "việt");
+//! ```
+
 mod common;
+mod config;
+mod convert;
 mod diacritics;
+mod encoding;
+mod english;
+mod exclusions;
 mod ffi;
+mod keymap;
+mod keystrokes;
+mod ms_vietnamese;
+mod settings;
+mod strip;
+mod syllable;
 mod telex;
+mod transliterate;
+mod viqr;
 mod vni;
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
 
 pub use ffi::VitypeTransformResult;
 
-// Re-export crate-visible types from common (keep public API surface minimal)
-pub(crate) use common::{
-    InputMethod, KeyTransformAction, OutputEncoding, TonePlacement, WTransformKind,
-};
+pub use common::{DeleteCountUnit, InputMethod, KeyTransformAction, OutputEncoding, TonePlacement};
+pub use config::{EngineConfig, EngineConfigBuilder};
+pub use convert::{convert_text, decode_text, detect_encoding, encode_text};
+pub use exclusions::{ExclusionKind, ExclusionList, ExclusionPattern};
+pub use keymap::{KeyAction, Keymap, KeymapBuilder, ToneMark};
+pub use keystrokes::to_keystrokes;
+pub use settings::{SettingsError, SETTINGS_VERSION};
+pub use strip::{remove_diacritics, remove_tones, slugify};
+pub use syllable::{check_spelling, SpellingError, Syllable};
+pub use transliterate::transliterate;
+
+pub(crate) use common::WTransformKind;
 
 // Use internal items from common
 use common::{is_vowel, lower_char, BASE_VOWELS, TONED_TO_BASE, VOWEL_TO_TONED};
+use diacritics::apply_tone;
+use encoding::EmittedText;
 
 // Use internal items from telex and vni
-use telex::is_telex_word_boundary;
-use vni::is_vni_word_boundary;
+use ms_vietnamese::is_ms_vietnamese_word_boundary;
+use telex::{is_telex_word_boundary, telex_bracket_vowel};
+use viqr::VIQR_KEYMAP;
+use vni::VNI_KEYMAP;
 
 // ==================== VitypeEngine ====================
 
@@ -38,41 +90,80 @@ enum HistorySegment {
     Boundary(Vec<char>),
 }
 
+/// Stateful keystroke processor for a single text input session.
+///
+/// Feed keys with [`process`](Self::process) and mirror host-side backspaces with
+/// [`delete_last_character`](Self::delete_last_character) so the engine's view of the
+/// current word stays in sync with the host's text.
+#[derive(Debug)]
 pub struct VitypeEngine {
     buffer: Vec<char>,
     raw_buffer: Vec<char>,
+    /// Every key typed for the current word, including escaped keys that `raw_buffer` drops.
+    word_keys: Vec<char>,
     history: VecDeque<HistorySegment>,
     is_foreign_mode: bool,
     transforms_locked: bool,
     last_transform_key: Option<char>,
     last_w_transform_kind: WTransformKind,
     suppressed_transform_key: Option<char>,
-    auto_fix_tone: bool,
-    free_tone_placement: bool,
-    tone_placement: TonePlacement,
-    output_encoding: OutputEncoding,
-    input_method: InputMethod,
+    pending_literal_key: bool,
+    /// Output text of each recently emitted character, oldest first.
+    emitted_text: Vec<EmittedText>,
+    config: EngineConfig,
+    word_lists: Arc<WordLists>,
+}
+
+/// User word lists, shared with the engines that replay a word after a backspace.
+#[derive(Clone, Debug, Default)]
+struct WordLists {
+    /// Words added with [`VitypeEngine::add_english_words`], lowercased.
+    english_words: HashSet<String>,
+    exclusions: ExclusionList,
+}
+
+impl Default for VitypeEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VitypeEngine {
-    pub(crate) fn new() -> Self {
+    /// Creates an engine with default settings: Telex, Unicode output, orthographic
+    /// tone placement, auto-fix tone enabled and free tone placement disabled.
+    pub fn new() -> Self {
+        Self::with_config(EngineConfig::default())
+    }
+
+    /// Creates an engine with the given settings.
+    pub fn with_config(config: EngineConfig) -> Self {
         Self {
             buffer: Vec::new(),
             raw_buffer: Vec::new(),
+            word_keys: Vec::new(),
             history: VecDeque::new(),
             is_foreign_mode: false,
             transforms_locked: false,
             last_transform_key: None,
             last_w_transform_kind: WTransformKind::None,
             suppressed_transform_key: None,
-            auto_fix_tone: true,
-            free_tone_placement: false,
-            tone_placement: TonePlacement::Orthographic,
-            output_encoding: OutputEncoding::Unicode,
-            input_method: InputMethod::Telex,
+            pending_literal_key: false,
+            emitted_text: Vec::new(),
+            config,
+            word_lists: Arc::default(),
         }
     }
 
+    /// Snapshot of the current settings.
+    pub fn config(&self) -> EngineConfig {
+        self.config.clone()
+    }
+
+    /// Replaces every setting at once. The word being typed is kept.
+    pub fn apply_config(&mut self, config: EngineConfig) {
+        self.config = config;
+    }
+
     fn return_action_or_fallback(
         &mut self,
         action: KeyTransformAction,
@@ -100,42 +191,187 @@ impl VitypeEngine {
         self.transforms_locked = true;
     }
 
-    pub(crate) fn set_auto_fix_tone(&mut self, enabled: bool) {
-        self.auto_fix_tone = enabled;
+    /// Moves an existing tone mark to the correct vowel as more vowels are typed
+    /// (`hoaf` + `i` → `hoài`). Enabled by default.
+    pub fn set_auto_fix_tone(&mut self, enabled: bool) {
+        self.config.auto_fix_tone = enabled;
+    }
+
+    /// Allows tone marks on vowel clusters that are not valid Vietnamese. Disabled by default.
+    pub fn set_free_tone_placement(&mut self, enabled: bool) {
+        self.config.free_tone_placement = enabled;
+    }
+
+    /// Types words that start with a foreign consonant (`f`, `j`, `z`, and `w` in Simple Telex)
+    /// literally, so `fix` stays `fix` instead of becoming `fĩ`. Disabled by default.
+    pub fn set_literal_foreign_initials(&mut self, enabled: bool) {
+        self.config.literal_foreign_initials = enabled;
+    }
+
+    /// Makes `[` `]` `{` `}` type ơ ư Ơ Ư in Telex, like UniKey. Pressing the bracket twice
+    /// types the bracket itself. Disabled by default.
+    pub fn set_telex_bracket_shortcuts(&mut self, enabled: bool) {
+        self.config.telex_bracket_shortcuts = enabled;
+    }
+
+    /// Expands doubled word-initial consonants in Telex (`cc` → ch, `gg` → gi, `kk` → kh,
+    /// `nn` → ng, `pp` → ph, `qq` → qu, `tt` → th). A third key restores the literal pair.
+    /// Disabled by default.
+    pub fn set_quick_telex(&mut self, enabled: bool) {
+        self.config.quick_telex = enabled;
+    }
+
+    /// Telex shortcuts for common consonants: a word-initial `f`/`j`/`w` types ph/gi/qu and a
+    /// `g`/`h`/`k` right after the vowel cluster types ng/nh/ch when the syllable stays valid.
+    /// Repeating the key types it literally. Disabled by default.
+    pub fn set_quick_consonants(&mut self, enabled: bool) {
+        self.config.quick_consonants = enabled;
+    }
+
+    /// A tone key is typed literally when the tone would leave the word misspelled: a final
+    /// p/t/c/ch with a tone other than sắc or nặng (`capf` stays `capf`), or an onset that is not
+    /// Vietnamese or is spelled wrong for its vowel (`ngis`). See [`crate::check_spelling`]. Disabled by
+    /// default.
+    pub fn set_check_spelling(&mut self, enabled: bool) {
+        self.config.check_spelling = enabled;
+    }
+
+    /// When a word boundary ends a word that the engine transformed into something that is not a
+    /// valid syllable (see [`crate::check_spelling`]), the boundary key returns an action that
+    /// puts back the keys as typed (`text` → `tẽt` → `text `). Words without a vowel, such as
+    /// `đ`, are kept. Disabled by default.
+    pub fn set_restore_invalid_words(&mut self, enabled: bool) {
+        self.config.restore_invalid_words = enabled;
+    }
+
+    /// Words whose keys spell a common English word (`mass`, `week`, `offer`) or one added with
+    /// [`add_english_words`](Self::add_english_words) are typed as-is: once the keys match, the
+    /// word is shown as typed and left alone, and at the word boundary a word that was edited into
+    /// one is put back like [`set_restore_invalid_words`](Self::set_restore_invalid_words) does.
+    /// Disabled by default.
+    pub fn set_english_bypass(&mut self, enabled: bool) {
+        self.config.english_bypass = enabled;
+    }
+
+    /// Switches the keymap used to interpret keystrokes. Telex by default.
+    pub fn set_input_method(&mut self, method: InputMethod) {
+        self.config.input_method = method;
+    }
+
+    /// Sets the encoding of the text returned by [`process_encoded`](Self::process_encoded) and
+    /// the C API. Unicode by default.
+    pub fn set_output_encoding(&mut self, encoding: OutputEncoding) {
+        self.config.output_encoding = encoding;
+    }
+
+    /// Unit of `delete_count` in [`process_encoded`](Self::process_encoded). Text already on
+    /// screen is re-measured in the new unit, so this can change at any time.
+    pub fn set_delete_count_unit(&mut self, unit: DeleteCountUnit) {
+        self.config.delete_count_unit = unit;
+    }
+
+    /// Sets which vowel of a cluster carries the tone mark. Orthographic by default.
+    pub fn set_tone_placement(&mut self, placement: TonePlacement) {
+        self.config.tone_placement = placement;
+    }
+
+    /// Whether [`set_auto_fix_tone`](Self::set_auto_fix_tone) is enabled.
+    pub fn auto_fix_tone(&self) -> bool {
+        self.config.auto_fix_tone
+    }
+
+    /// Whether [`set_free_tone_placement`](Self::set_free_tone_placement) is enabled.
+    pub fn free_tone_placement(&self) -> bool {
+        self.config.free_tone_placement
+    }
+
+    /// Whether [`set_literal_foreign_initials`](Self::set_literal_foreign_initials) is enabled.
+    pub fn literal_foreign_initials(&self) -> bool {
+        self.config.literal_foreign_initials
+    }
+
+    /// Whether [`set_telex_bracket_shortcuts`](Self::set_telex_bracket_shortcuts) is enabled.
+    pub fn telex_bracket_shortcuts(&self) -> bool {
+        self.config.telex_bracket_shortcuts
+    }
+
+    /// Whether [`set_quick_telex`](Self::set_quick_telex) is enabled.
+    pub fn quick_telex(&self) -> bool {
+        self.config.quick_telex
+    }
+
+    /// Whether [`set_quick_consonants`](Self::set_quick_consonants) is enabled.
+    pub fn quick_consonants(&self) -> bool {
+        self.config.quick_consonants
     }
 
-    pub(crate) fn set_free_tone_placement(&mut self, enabled: bool) {
-        self.free_tone_placement = enabled;
+    /// Whether [`set_check_spelling`](Self::set_check_spelling) is enabled.
+    pub fn check_spelling(&self) -> bool {
+        self.config.check_spelling
     }
 
-    pub(crate) fn set_input_method(&mut self, method: InputMethod) {
-        self.input_method = method;
+    /// Whether [`set_restore_invalid_words`](Self::set_restore_invalid_words) is enabled.
+    pub fn restore_invalid_words(&self) -> bool {
+        self.config.restore_invalid_words
     }
 
-    pub(crate) fn set_output_encoding(&mut self, encoding: OutputEncoding) {
-        self.output_encoding = encoding;
+    /// Whether [`set_english_bypass`](Self::set_english_bypass) is enabled.
+    pub fn english_bypass(&self) -> bool {
+        self.config.english_bypass
     }
 
-    pub(crate) fn set_tone_placement(&mut self, placement: TonePlacement) {
-        self.tone_placement = placement;
+    /// Keymap used to interpret keystrokes.
+    pub fn input_method(&self) -> InputMethod {
+        self.config.input_method.clone()
     }
 
-    pub(crate) fn output_encoding(&self) -> OutputEncoding {
-        self.output_encoding
+    /// Encoding hosts are expected to emit. [`process`](Self::process) always returns
+    /// precomposed text; [`process_encoded`](Self::process_encoded) and the C API convert it to
+    /// this encoding.
+    pub fn output_encoding(&self) -> OutputEncoding {
+        self.config.output_encoding
     }
 
-    pub(crate) fn process(&mut self, input: &str) -> Option<KeyTransformAction> {
+    /// Unit of `delete_count` in [`process_encoded`](Self::process_encoded).
+    pub fn delete_count_unit(&self) -> DeleteCountUnit {
+        self.config.delete_count_unit
+    }
+
+    /// Rule used to choose which vowel of a cluster carries the tone mark.
+    pub fn tone_placement(&self) -> TonePlacement {
+        self.config.tone_placement
+    }
+
+    /// Processes one keystroke.
+    ///
+    /// `input` must be exactly one character; anything else is ignored and returns `None`.
+    /// `None` means the host should insert the key unchanged. `Some(action)` means the key
+    /// is consumed and the host should apply `action` instead.
+    pub fn process(&mut self, input: &str) -> Option<KeyTransformAction> {
         let mut chars = input.chars();
         let ch = chars.next()?;
         if chars.next().is_some() {
             return None;
         }
 
-        if is_word_boundary(ch, self.input_method) {
-            self.commit_current_word_to_history_if_needed();
-            self.push_boundary_to_history(ch);
-            self.reset_current_word();
-            return None;
+        if self.pending_literal_key {
+            self.pending_literal_key = false;
+            return self.insert_literal_key(ch);
+        }
+
+        if self.is_literal_prefix_key(ch) {
+            // The prefix itself is never shown; the next key is inserted as typed.
+            self.pending_literal_key = true;
+            return Some(KeyTransformAction {
+                delete_count: 0,
+                text: String::new(),
+            });
+        }
+
+        if self.is_word_boundary_in_context(ch) {
+            let action = self.restore_raw_word_at_boundary(ch);
+            self.end_word_with_boundary(ch);
+            return action;
         }
 
         let previous_buffer_count = self.buffer.len();
@@ -147,6 +383,15 @@ impl VitypeEngine {
         }
 
         self.raw_buffer.push(ch);
+        self.word_keys.push(ch);
+
+        if self.buffer.is_empty() && self.is_foreign_initial(ch) {
+            self.is_foreign_mode = true;
+        }
+
+        if !self.is_foreign_mode && self.is_typed_as_is(&self.word_keys) {
+            return self.show_word_keys_as_foreign(previous_buffer_count);
+        }
 
         if self.is_foreign_mode || self.transforms_locked {
             self.buffer.push(ch);
@@ -161,7 +406,7 @@ impl VitypeEngine {
         self.buffer.push(ch);
 
         if self.suppressed_transform_key == Some(ch_lower) {
-            if self.auto_fix_tone && is_vowel(ch) {
+            if self.config.auto_fix_tone && is_vowel(ch) {
                 if let Some(action) = self.reposition_tone_if_needed(true, None) {
                     return self.return_action_or_fallback(action, previous_buffer_count);
                 }
@@ -182,11 +427,20 @@ impl VitypeEngine {
             return self.return_action_or_fallback(action, previous_buffer_count);
         }
 
+        if self.is_modifier_key(ch) {
+            // A modifier that did not apply to the word is plain punctuation after all.
+            self.buffer.pop();
+            self.raw_buffer.pop();
+            self.clear_last_transform_state();
+            self.end_word_with_boundary(ch);
+            return None;
+        }
+
         if let Some(action) = self.try_auto_fix_uhorn_o_before_consonant(ch) {
             return self.return_action_or_fallback(action, previous_buffer_count);
         }
 
-        if self.auto_fix_tone {
+        if self.config.auto_fix_tone {
             if let Some(action) = self.reposition_tone_if_needed(true, None) {
                 return self.return_action_or_fallback(action, previous_buffer_count);
             }
@@ -199,33 +453,120 @@ impl VitypeEngine {
     // ==================== Dispatch Methods ====================
 
     fn try_escape_sequence(&mut self, ch: char) -> Option<KeyTransformAction> {
-        match self.input_method {
-            InputMethod::Telex => self.try_telex_escape_sequence(ch),
-            InputMethod::Vni => self.try_vni_escape_sequence(ch),
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex => self.try_telex_escape_sequence(ch),
+            InputMethod::MicrosoftVietnamese => None,
+            InputMethod::Vni | InputMethod::Viqr | InputMethod::Custom(_) => {
+                self.try_keymap_escape_sequence(ch)
+            }
         }
     }
 
     fn try_consonant_transform(&mut self, ch: char) -> Option<KeyTransformAction> {
-        match self.input_method {
-            InputMethod::Telex => self.try_telex_consonant_transform(ch),
-            InputMethod::Vni => self.try_vni_consonant_transform(ch),
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex => self.try_telex_consonant_transform(ch),
+            InputMethod::MicrosoftVietnamese => None,
+            InputMethod::Vni | InputMethod::Viqr | InputMethod::Custom(_) => {
+                self.try_keymap_consonant_transform(ch)
+            }
         }
     }
 
     fn try_vowel_transform(&mut self, ch: char) -> Option<KeyTransformAction> {
-        match self.input_method {
-            InputMethod::Telex => self.try_telex_vowel_transform(ch),
-            InputMethod::Vni => self.try_vni_vowel_transform(ch),
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex => self.try_telex_vowel_transform(ch),
+            InputMethod::MicrosoftVietnamese => None,
+            InputMethod::Vni | InputMethod::Viqr | InputMethod::Custom(_) => {
+                self.try_keymap_vowel_transform(ch)
+            }
         }
     }
 
     fn try_tone_mark(&mut self, ch: char) -> Option<KeyTransformAction> {
-        match self.input_method {
-            InputMethod::Telex => self.try_telex_tone_mark(ch),
-            InputMethod::Vni => self.try_vni_tone_mark(ch),
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex => self.try_telex_tone_mark(ch),
+            InputMethod::MicrosoftVietnamese => self.try_ms_vietnamese_tone_mark(ch),
+            InputMethod::Vni | InputMethod::Viqr | InputMethod::Custom(_) => {
+                self.try_keymap_tone_mark(ch)
+            }
+        }
+    }
+
+    /// First key of a word that marks the whole word as foreign.
+    fn is_foreign_initial(&self, ch: char) -> bool {
+        if !self.config.literal_foreign_initials {
+            return false;
+        }
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex => self.is_telex_foreign_initial(ch),
+            InputMethod::Vni
+            | InputMethod::Viqr
+            | InputMethod::MicrosoftVietnamese
+            | InputMethod::Custom(_) => false,
         }
     }
 
+    /// Punctuation keys that act as diacritics inside a word and as boundaries elsewhere.
+    fn is_modifier_key(&self, ch: char) -> bool {
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex | InputMethod::MicrosoftVietnamese => {
+                false
+            }
+            InputMethod::Vni | InputMethod::Viqr | InputMethod::Custom(_) => {
+                self.is_keymap_modifier_key(ch)
+            }
+        }
+    }
+
+    /// Punctuation key that types a vowel (Telex bracket shortcuts).
+    fn is_vowel_shortcut_key(&self, ch: char) -> bool {
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex => {
+                self.config.telex_bracket_shortcuts && telex_bracket_vowel(ch).is_some()
+            }
+            InputMethod::Vni
+            | InputMethod::Viqr
+            | InputMethod::MicrosoftVietnamese
+            | InputMethod::Custom(_) => false,
+        }
+    }
+
+    /// Key that makes the following key literal (VIQR `\`).
+    fn is_literal_prefix_key(&self, ch: char) -> bool {
+        match self.config.input_method {
+            InputMethod::Telex | InputMethod::SimpleTelex | InputMethod::MicrosoftVietnamese => {
+                false
+            }
+            InputMethod::Vni | InputMethod::Viqr | InputMethod::Custom(_) => {
+                self.is_keymap_literal_prefix_key(ch)
+            }
+        }
+    }
+
+    fn is_word_boundary_in_context(&self, ch: char) -> bool {
+        if !is_word_boundary(ch, &self.config.input_method) {
+            return false;
+        }
+        let is_transforming = !self.is_foreign_mode && !self.transforms_locked;
+        if is_transforming && self.is_vowel_shortcut_key(ch) {
+            return false;
+        }
+        let is_composing = !self.buffer.is_empty() && is_transforming;
+        !(is_composing && self.is_modifier_key(ch))
+    }
+
+    fn insert_literal_key(&mut self, ch: char) -> Option<KeyTransformAction> {
+        if is_word_boundary(ch, &self.config.input_method) {
+            self.end_word_with_boundary(ch);
+            return None;
+        }
+        self.buffer.push(ch);
+        self.raw_buffer.push(ch);
+        self.word_keys.push(ch);
+        self.clear_last_transform_state();
+        None
+    }
+
     // ==================== Common Engine Methods ====================
 
     fn reposition_tone_if_needed(
@@ -348,7 +689,7 @@ impl VitypeEngine {
         self.buffer[o_index] = new_o;
         self.clear_last_transform_state();
 
-        if self.auto_fix_tone {
+        if self.config.auto_fix_tone {
             if let Some(action) = self.reposition_tone_if_needed(true, Some(o_index)) {
                 return Some(action);
             }
@@ -547,7 +888,7 @@ impl VitypeEngine {
         self.last_transform_key = Some(trigger_key);
         self.last_w_transform_kind = WTransformKind::CompoundUoiw;
 
-        if self.auto_fix_tone {
+        if self.config.auto_fix_tone {
             if let Some(action) = self.reposition_tone_if_needed(false, Some(u_index)) {
                 return Some(action);
             }
@@ -857,7 +1198,7 @@ impl VitypeEngine {
         self.last_transform_key = Some(trigger_key);
         self.last_w_transform_kind = WTransformKind::CompoundUow;
 
-        if self.auto_fix_tone {
+        if self.config.auto_fix_tone {
             if let Some(action) = self.reposition_tone_if_needed(false, Some(first_u_index)) {
                 return Some(action);
             }
@@ -915,32 +1256,6 @@ impl VitypeEngine {
         has_gap
     }
 
-    fn is_u_vowel_after_q(&self, vowel_index: usize) -> bool {
-        if vowel_index == 0 {
-            return false;
-        }
-        let ch = self.buffer[vowel_index];
-        let base_vowel = self.get_base_vowel(ch);
-        if lower_char(base_vowel) != 'u' {
-            return false;
-        }
-        let prev_char = self.buffer[vowel_index - 1];
-        prev_char == 'q' || prev_char == 'Q'
-    }
-
-    fn is_gi_i_candidate(&self, vowel_index: usize) -> bool {
-        if vowel_index == 0 {
-            return false;
-        }
-        let ch = self.buffer[vowel_index];
-        let base_vowel = self.get_base_vowel(ch);
-        if lower_char(base_vowel) != 'i' {
-            return false;
-        }
-        let prev_char = self.buffer[vowel_index - 1];
-        prev_char == 'g' || prev_char == 'G'
-    }
-
     fn is_nucleus_only_vowel(&self, ch: char) -> bool {
         matches!(
             self.get_base_vowel(ch),
@@ -948,87 +1263,33 @@ impl VitypeEngine {
         )
     }
 
-    fn normalize_vowel_for_cluster(ch: char) -> Option<char> {
-        let lower = lower_char(ch);
-        Some(match lower {
-            'a' | 'e' | 'i' | 'o' | 'u' | 'y' => lower,
-            'ă' | 'â' => 'a',
-            'ê' => 'e',
-            'ô' | 'ơ' => 'o',
-            'ư' => 'u',
-            _ => return None,
-        })
-    }
-
-    fn is_valid_vowel_cluster_chars(cluster: &[char]) -> bool {
-        match cluster {
-            ['a', 'i']
-            | ['a', 'o']
-            | ['a', 'u']
-            | ['a', 'y']
-            | ['e', 'o']
-            | ['e', 'u']
-            | ['i', 'u']
-            | ['i', 'a']
-            | ['i', 'e']
-            | ['y', 'e']
-            | ['o', 'a']
-            | ['o', 'e']
-            | ['o', 'i']
-            | ['u', 'a']
-            | ['u', 'o']
-            | ['u', 'e']
-            | ['u', 'i']
-            | ['u', 'y']
-            | ['u', 'u']
-            | ['i', 'e', 'u']
-            | ['y', 'e', 'u']
-            | ['o', 'a', 'i']
-            | ['o', 'a', 'y']
-            | ['u', 'o', 'i']
-            | ['u', 'y', 'a']
-            | ['u', 'y', 'e']
-            | ['u', 'o', 'u'] => true,
-            _ => false,
-        }
-    }
-
-    fn is_valid_tone_cluster(&self, before: usize) -> bool {
-        let mut vowel_indices: Vec<usize> = Vec::new();
-        self.for_each_effective_vowel_index(before, |index| {
-            vowel_indices.push(index);
-            true
-        });
-
-        if vowel_indices.is_empty() {
-            return false;
-        }
-
-        if vowel_indices.len() == 1 {
-            return true;
-        }
-
-        if vowel_indices.len() > 3 {
-            return false;
-        }
-
-        for window in vowel_indices.windows(2) {
-            if window[1] != window[0] + 1 {
-                return false;
+    /// Indices of the vowels that can carry the tone of the word before `before`, taken from
+    /// [`Syllable::parse`]. With `free_tone_placement`, a word the parser rejects still offers
+    /// every vowel of its clusters.
+    fn tone_vowel_indices(&self, before: usize) -> Option<Vec<usize>> {
+        let limit = before.min(self.buffer.len());
+        let word: String = self.buffer[..limit]
+            .iter()
+            .map(|&ch| self.get_base_vowel(ch))
+            .collect();
+        match Syllable::parse(&word) {
+            Some(syllable) => {
+                let start = syllable.onset.chars().count();
+                let count = usize::from(syllable.glide.is_some())
+                    + syllable.nucleus.chars().count()
+                    + syllable.coda.chars().take_while(|&ch| is_vowel(ch)).count();
+                Some((start..start + count).collect())
             }
+            None if self.config.free_tone_placement => {
+                let mut indices = Vec::new();
+                self.for_each_effective_vowel_index(before, |index| {
+                    indices.push(index);
+                    true
+                });
+                Some(indices)
+            }
+            None => None,
         }
-
-        let mut cluster: Vec<char> = Vec::with_capacity(vowel_indices.len());
-        for index in vowel_indices {
-            let base_vowel = self.get_base_vowel(self.buffer[index]);
-            let normalized = match Self::normalize_vowel_for_cluster(base_vowel) {
-                Some(ch) => ch,
-                None => return false,
-            };
-            cluster.push(normalized);
-        }
-
-        Self::is_valid_vowel_cluster_chars(&cluster)
     }
 
     fn for_each_effective_vowel_index<F>(&self, before: usize, mut f: F)
@@ -1036,84 +1297,35 @@ impl VitypeEngine {
         F: FnMut(usize) -> bool,
     {
         let limit = before.min(self.buffer.len());
-        let mut pending_gi_i: Option<usize> = None;
-
-        for index in 0..limit {
-            if !is_vowel(self.buffer[index]) {
-                continue;
-            }
-
-            if pending_gi_i.is_some() {
-                pending_gi_i = None;
-            }
-
-            if self.is_u_vowel_after_q(index) {
-                continue;
-            }
-
-            if self.is_gi_i_candidate(index) {
-                pending_gi_i = Some(index);
-                continue;
-            }
-
+        for index in syllable::vowel_indices(&self.buffer[..limit]) {
             if !f(index) {
                 return;
             }
         }
-
-        if let Some(index) = pending_gi_i {
-            let _ = f(index);
-        }
     }
 
     fn find_target_vowel_index(&self, before: usize) -> Option<usize> {
-        if !self.free_tone_placement && !self.is_valid_tone_cluster(before) {
-            return None;
-        }
-
-        let mut vowel_count = 0usize;
-        let mut first_index: usize = 0;
-        let mut second_index: usize = 0;
-        let mut first_base_lower: char = '\0';
-        let mut second_base_lower: char = '\0';
-        let mut last_nucleus_only_index: Option<usize> = None;
-
-        self.for_each_effective_vowel_index(before, |index| {
-            vowel_count += 1;
-            if vowel_count == 1 {
-                first_index = index;
-                first_base_lower = lower_char(self.get_base_vowel(self.buffer[index]));
-            } else if vowel_count == 2 {
-                second_index = index;
-                second_base_lower = lower_char(self.get_base_vowel(self.buffer[index]));
-            }
-
-            if self.is_nucleus_only_vowel(self.buffer[index]) {
-                last_nucleus_only_index = Some(index);
-            }
-            true
-        });
-
-        if vowel_count == 0 {
-            return None;
-        }
-
-        if vowel_count == 1 {
+        let vowel_indices = self.tone_vowel_indices(before)?;
+        let (&first_index, rest) = vowel_indices.split_first()?;
+        if rest.is_empty() {
             return Some(first_index);
         }
 
-        if let Some(index) = last_nucleus_only_index {
+        if let Some(&index) = vowel_indices
+            .iter()
+            .rev()
+            .find(|&&index| self.is_nucleus_only_vowel(self.buffer[index]))
+        {
             return Some(index);
         }
 
-        if vowel_count == 2 {
-            if self.tone_placement == TonePlacement::NucleusOnly {
+        if let [second_index] = *rest {
+            if self.config.tone_placement == TonePlacement::NucleusOnly {
                 // Nucleus-only overrides for the vowel clusters where the orthographic rules
                 // may place tone on a glide-like vowel ("oa", "oe", "uy").
-                if (first_base_lower == 'u' && second_base_lower == 'y')
-                    || (first_base_lower == 'o'
-                        && (second_base_lower == 'a' || second_base_lower == 'e'))
-                {
+                let first_base = self.get_base_vowel(self.buffer[first_index]);
+                let second_base = self.get_base_vowel(self.buffer[second_index]);
+                if syllable::is_glide(first_base, second_base) {
                     return Some(second_index);
                 }
             }
@@ -1127,18 +1339,31 @@ impl VitypeEngine {
             return Some(first_index);
         }
 
-        let target_position = (vowel_count - 1) / 2;
-        let mut position = 0usize;
-        let mut target_index: Option<usize> = None;
-        self.for_each_effective_vowel_index(before, |index| {
-            if position == target_position {
-                target_index = Some(index);
-                return false;
-            }
-            position += 1;
-            true
-        });
-        target_index
+        vowel_indices.get((vowel_indices.len() - 1) / 2).copied()
+    }
+
+    /// Whether the word before `end`, with `tone_key` on the vowel at `vowel_index` as its only
+    /// tone, passes the spelling check as far as its letters so far can tell. A missing or
+    /// unfinished coda is not an error yet, since it may still be typed.
+    fn is_tone_spelled_correctly(&self, vowel_index: usize, end: usize, tone_key: char) -> bool {
+        let word: String = self.buffer[..end]
+            .iter()
+            .enumerate()
+            .map(|(index, &ch)| {
+                let base = self.get_base_vowel(ch);
+                if index == vowel_index {
+                    apply_tone(base, Some(tone_key)).unwrap_or(ch)
+                } else {
+                    base
+                }
+            })
+            .collect();
+        !matches!(
+            check_spelling(&word),
+            Err(SpellingError::InvalidOnset(_)
+                | SpellingError::OnsetSpelling { .. }
+                | SpellingError::ToneNotAllowed { .. })
+        )
     }
 
     fn find_last_toned_vowel_index(&self) -> Option<usize> {
@@ -1165,6 +1390,13 @@ impl VitypeEngine {
         let vowel_index = self.find_target_vowel_index(trigger_index)?;
         let vowel = self.buffer[vowel_index];
 
+        if self.config.check_spelling
+            && tone_key != 'z'
+            && !self.is_tone_spelled_correctly(vowel_index, trigger_index, tone_key)
+        {
+            return None;
+        }
+
         let mut start_index = vowel_index;
         if let Some(earliest) = self.clear_other_tones(vowel_index, trigger_index) {
             if earliest < start_index {
@@ -1407,7 +1639,7 @@ impl VitypeEngine {
             if let Some((base, _)) = TONED_TO_BASE.get(&ch) {
                 if self.buffer[idx] != *base {
                     self.buffer[idx] = *base;
-                    if earliest.map_or(true, |current| idx < current) {
+                    if earliest.is_none_or(|current| idx < current) {
                         earliest = Some(idx);
                     }
                 }
@@ -1494,17 +1726,85 @@ impl VitypeEngine {
         self.buffer[start..].iter().collect()
     }
 
+    fn end_word_with_boundary(&mut self, ch: char) {
+        self.commit_current_word_to_history_if_needed();
+        self.push_boundary_to_history(ch);
+        self.reset_current_word();
+    }
+
+    /// Words starting with an excluded prefix, and English words with `english_bypass`, are
+    /// never transformed. Excluded whole words are restored at the word boundary.
+    fn is_typed_as_is(&self, keys: &[char]) -> bool {
+        self.is_excluded_prefix(keys) || (self.config.english_bypass && self.is_english_word(keys))
+    }
+
+    /// Switches the current word to foreign mode, showing every key typed for it, once
+    /// [`is_typed_as_is`](Self::is_typed_as_is) matches them.
+    fn show_word_keys_as_foreign(
+        &mut self,
+        previous_buffer_count: usize,
+    ) -> Option<KeyTransformAction> {
+        let shown_as_typed = self.buffer.len() + 1 == self.word_keys.len()
+            && self.word_keys.starts_with(&self.buffer);
+        self.is_foreign_mode = true;
+        self.clear_transform_state();
+        self.buffer = self.word_keys.clone();
+        self.raw_buffer = self.word_keys.clone();
+
+        if shown_as_typed {
+            // Nothing was transformed; the host inserts the key itself.
+            return None;
+        }
+        Some(KeyTransformAction {
+            delete_count: previous_buffer_count,
+            text: self.buffer.iter().collect(),
+        })
+    }
+
+    /// With `restore_invalid_words`, turns a finished word that is not a valid syllable back
+    /// into its raw keys and returns the action that shows them, followed by `boundary`. Does
+    /// the same for excluded words and, with `english_bypass`, English words.
+    fn restore_raw_word_at_boundary(&mut self, boundary: char) -> Option<KeyTransformAction> {
+        if self.buffer == self.raw_buffer {
+            return None;
+        }
+        let is_as_is = self.is_excluded(&self.raw_buffer)
+            || (self.config.english_bypass && self.is_english_word_at_boundary());
+        if !is_as_is && !self.is_invalid_word() {
+            return None;
+        }
+
+        let delete_count = self.buffer.len();
+        self.buffer = self.raw_buffer.clone();
+        self.is_foreign_mode = true;
+        let mut text: String = self.raw_buffer.iter().collect();
+        text.push(boundary);
+        Some(KeyTransformAction { delete_count, text })
+    }
+
+    fn is_invalid_word(&self) -> bool {
+        if !self.config.restore_invalid_words || !self.buffer.iter().any(|&ch| is_vowel(ch)) {
+            return false;
+        }
+        let word: String = self.buffer.iter().collect();
+        check_spelling(&word).is_err()
+    }
+
     fn reset_current_word(&mut self) {
         self.buffer.clear();
         self.raw_buffer.clear();
+        self.word_keys.clear();
         self.clear_transform_state();
         self.is_foreign_mode = false;
         self.transforms_locked = false;
     }
 
-    pub(crate) fn reset(&mut self) {
+    /// Forgets the current word and the recent-word history, e.g. after the caret moves.
+    pub fn reset(&mut self) {
         self.reset_current_word();
+        self.pending_literal_key = false;
         self.history.clear();
+        self.emitted_text.clear();
     }
 
     fn commit_current_word_to_history_if_needed(&mut self) {
@@ -1566,6 +1866,7 @@ impl VitypeEngine {
         match self.history.pop_back() {
             Some(HistorySegment::Word(word)) => {
                 self.buffer = word.buffer;
+                self.word_keys = word.raw_buffer.clone();
                 self.raw_buffer = word.raw_buffer;
                 self.is_foreign_mode = word.is_foreign_mode;
                 self.transforms_locked = word.transforms_locked;
@@ -1581,11 +1882,8 @@ impl VitypeEngine {
     }
 
     fn rebuild_current_word_from_raw(&self, raw: &[char]) -> VitypeEngine {
-        let mut engine = VitypeEngine::new();
-        engine.auto_fix_tone = self.auto_fix_tone;
-        engine.tone_placement = self.tone_placement;
-        engine.output_encoding = self.output_encoding;
-        engine.input_method = self.input_method;
+        let mut engine = VitypeEngine::with_config(self.config.clone());
+        engine.word_lists = Arc::clone(&self.word_lists);
 
         for ch in raw {
             let input = ch.to_string();
@@ -1598,6 +1896,7 @@ impl VitypeEngine {
     fn adopt_current_word_state_from(&mut self, other: VitypeEngine) {
         self.buffer = other.buffer;
         self.raw_buffer = other.raw_buffer;
+        self.word_keys = other.word_keys;
         self.is_foreign_mode = other.is_foreign_mode;
         self.transforms_locked = other.transforms_locked;
         self.last_transform_key = other.last_transform_key;
@@ -1633,6 +1932,7 @@ impl VitypeEngine {
         // Fallback: best-effort single raw deletion.
         self.buffer = desired_buffer;
         self.raw_buffer.pop();
+        self.word_keys = self.raw_buffer.clone();
         self.clear_transform_state();
         self.is_foreign_mode = self.has_multiple_vowel_clusters(self.buffer.len());
         if self.buffer.is_empty() {
@@ -1640,7 +1940,16 @@ impl VitypeEngine {
         }
     }
 
-    pub(crate) fn delete_last_character(&mut self) {
+    /// Mirrors a host-side backspace. Crossing a word boundary restores the previous
+    /// word so it can still be edited with tone and shape keys.
+    ///
+    /// The engine drops the whole last letter, while the encoded output it remembers for
+    /// [`process_encoded`](Self::process_encoded) loses one [`DeleteCountUnit`], so later
+    /// delete counts still match what the host shows.
+    #[allow(clippy::collapsible_match)]
+    pub fn delete_last_character(&mut self) {
+        self.pending_literal_key = false;
+        self.record_backspace();
         if !self.buffer.is_empty() {
             self.delete_last_character_in_current_word();
             return;
@@ -1677,10 +1986,13 @@ impl VitypeEngine {
 
 // ==================== Helper Functions ====================
 
-fn is_word_boundary(ch: char, input_method: InputMethod) -> bool {
+fn is_word_boundary(ch: char, input_method: &InputMethod) -> bool {
     match input_method {
-        InputMethod::Telex => is_telex_word_boundary(ch),
-        InputMethod::Vni => is_vni_word_boundary(ch),
+        InputMethod::Telex | InputMethod::SimpleTelex => is_telex_word_boundary(ch),
+        InputMethod::Vni => VNI_KEYMAP.is_word_boundary(ch),
+        InputMethod::Viqr => VIQR_KEYMAP.is_word_boundary(ch),
+        InputMethod::MicrosoftVietnamese => is_ms_vietnamese_word_boundary(ch),
+        InputMethod::Custom(keymap) => keymap.is_word_boundary(ch),
     }
 }
 
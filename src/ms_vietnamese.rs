@@ -0,0 +1,39 @@
+use crate::common::KeyTransformAction;
+use crate::VitypeEngine;
+
+// ==================== Microsoft Vietnamese Helper Functions ====================
+
+pub(super) fn is_ms_vietnamese_word_boundary(ch: char) -> bool {
+    ch.is_ascii_whitespace() || ch.is_ascii_punctuation() || ch.is_ascii_digit()
+}
+
+/// Maps the combining tone mark sent by a tone key to the internal (Telex) tone key.
+fn ms_tone_mark_to_internal(ch: char) -> Option<char> {
+    Some(match ch {
+        '\u{0301}' => 's', // sắc
+        '\u{0300}' => 'f', // huyền
+        '\u{0309}' => 'r', // hỏi
+        '\u{0303}' => 'x', // ngã
+        '\u{0323}' => 'j', // nặng
+        _ => return None,
+    })
+}
+
+// ==================== Microsoft Vietnamese Methods on VitypeEngine ====================
+
+impl VitypeEngine {
+    pub(super) fn try_ms_vietnamese_tone_mark(&mut self, ch: char) -> Option<KeyTransformAction> {
+        let internal_tone_key = ms_tone_mark_to_internal(ch)?;
+
+        // Pressing the same tone key twice removes the tone; a second combining mark
+        // would only stack on the first.
+        if self.last_transform_key == Some(ch) {
+            if let Some(action) = self.apply_tone_mark_internal('z', ch) {
+                self.clear_last_transform_state();
+                return Some(action);
+            }
+        }
+
+        self.apply_tone_mark_internal(internal_tone_key, ch)
+    }
+}
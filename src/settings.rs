@@ -0,0 +1,352 @@
+//! Versioned on-disk format for [`EngineConfig`].
+//!
+//! The same keys are used in TOML and JSON; missing keys keep their default value and
+//! unknown keys are ignored so that older builds can read files written by newer ones.
+//!
+//! ```toml
+//! version = 1
+//! input_method = "telex"            # "telex" | "simple_telex" | "vni" | "viqr"
+//!                                   # | "microsoft_vietnamese" | "custom"
+//! output_encoding = "unicode"       # "unicode" | "composite_unicode" | "tcvn3" | "viscii"
+//!                                   # | "vni_windows" | "viqr" | "html_entity"
+//!                                   # | "unicode_escape" | "cp1258"
+//! delete_count_unit = "code_points" # "code_points" | "utf16_code_units" | "utf8_bytes"
+//!                                   # | "grapheme_clusters"
+//! tone_placement = "orthographic"   # "orthographic" | "nucleus_only"
+//! auto_fix_tone = true
+//! free_tone_placement = false
+//! literal_foreign_initials = false
+//! telex_bracket_shortcuts = false
+//! quick_telex = false
+//! quick_consonants = false
+//! check_spelling = false
+//! restore_invalid_words = false
+//! english_bypass = false
+//! ```
+//!
+//! With `input_method = "custom"` the keymap is stored inline as a `[keymap]` table using the
+//! keymap file format (see [`crate::Keymap`]), minus its `version` key.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{DeleteCountUnit, InputMethod, OutputEncoding, TonePlacement};
+use crate::config::EngineConfig;
+use crate::keymap::KeymapFile;
+
+/// Settings format version written by this build. Files with a newer version are rejected.
+pub const SETTINGS_VERSION: u32 = 1;
+
+// ==================== Errors ====================
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(io::Error),
+    /// The file is not valid TOML/JSON or a key has the wrong type.
+    Parse(String),
+    /// The file has no `version` key.
+    MissingVersion,
+    /// The file was written by a newer, incompatible format version.
+    UnsupportedVersion(u32),
+    /// A key holds a value this build does not recognize.
+    InvalidValue {
+        key: &'static str,
+        value: String,
+    },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io(err) => write!(f, "cannot access settings file: {}", err),
+            SettingsError::Parse(message) => write!(f, "malformed settings: {}", message),
+            SettingsError::MissingVersion => write!(f, "settings have no `version` key"),
+            SettingsError::UnsupportedVersion(version) => write!(
+                f,
+                "settings version {} is newer than supported version {}",
+                version, SETTINGS_VERSION
+            ),
+            SettingsError::InvalidValue { key, value } => {
+                write!(f, "invalid value `{}` for `{}`", value, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SettingsError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SettingsError {
+    fn from(err: io::Error) -> Self {
+        SettingsError::Io(err)
+    }
+}
+
+// ==================== Serialized Form ====================
+
+#[derive(Serialize, Deserialize, Default)]
+struct SettingsFile {
+    version: Option<u32>,
+    input_method: Option<String>,
+    output_encoding: Option<String>,
+    delete_count_unit: Option<String>,
+    tone_placement: Option<String>,
+    auto_fix_tone: Option<bool>,
+    free_tone_placement: Option<bool>,
+    literal_foreign_initials: Option<bool>,
+    telex_bracket_shortcuts: Option<bool>,
+    quick_telex: Option<bool>,
+    quick_consonants: Option<bool>,
+    check_spelling: Option<bool>,
+    restore_invalid_words: Option<bool>,
+    english_bypass: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keymap: Option<KeymapFile>,
+}
+
+impl SettingsFile {
+    fn from_config(config: &EngineConfig) -> Self {
+        Self {
+            version: Some(SETTINGS_VERSION),
+            input_method: Some(input_method_name(&config.input_method).to_string()),
+            output_encoding: Some(output_encoding_name(config.output_encoding).to_string()),
+            delete_count_unit: Some(delete_count_unit_name(config.delete_count_unit).to_string()),
+            tone_placement: Some(tone_placement_name(config.tone_placement).to_string()),
+            auto_fix_tone: Some(config.auto_fix_tone),
+            free_tone_placement: Some(config.free_tone_placement),
+            literal_foreign_initials: Some(config.literal_foreign_initials),
+            telex_bracket_shortcuts: Some(config.telex_bracket_shortcuts),
+            quick_telex: Some(config.quick_telex),
+            quick_consonants: Some(config.quick_consonants),
+            check_spelling: Some(config.check_spelling),
+            restore_invalid_words: Some(config.restore_invalid_words),
+            english_bypass: Some(config.english_bypass),
+            keymap: match &config.input_method {
+                InputMethod::Custom(keymap) => Some(KeymapFile::from_keymap(keymap, false)),
+                _ => None,
+            },
+        }
+    }
+
+    fn into_config(self) -> Result<EngineConfig, SettingsError> {
+        let version = self.version.ok_or(SettingsError::MissingVersion)?;
+        if version > SETTINGS_VERSION {
+            return Err(SettingsError::UnsupportedVersion(version));
+        }
+
+        let mut config = EngineConfig::default();
+        if let Some(value) = self.input_method {
+            config.input_method = if value == "custom" {
+                let keymap = self.keymap.ok_or(SettingsError::InvalidValue {
+                    key: "input_method",
+                    value,
+                })?;
+                InputMethod::Custom(Arc::new(keymap.into_keymap(false)?))
+            } else {
+                parse_input_method(&value).ok_or(SettingsError::InvalidValue {
+                    key: "input_method",
+                    value,
+                })?
+            };
+        }
+        if let Some(value) = self.output_encoding {
+            config.output_encoding =
+                parse_output_encoding(&value).ok_or(SettingsError::InvalidValue {
+                    key: "output_encoding",
+                    value,
+                })?;
+        }
+        if let Some(value) = self.delete_count_unit {
+            config.delete_count_unit =
+                parse_delete_count_unit(&value).ok_or(SettingsError::InvalidValue {
+                    key: "delete_count_unit",
+                    value,
+                })?;
+        }
+        if let Some(value) = self.tone_placement {
+            config.tone_placement =
+                parse_tone_placement(&value).ok_or(SettingsError::InvalidValue {
+                    key: "tone_placement",
+                    value,
+                })?;
+        }
+        if let Some(value) = self.auto_fix_tone {
+            config.auto_fix_tone = value;
+        }
+        if let Some(value) = self.free_tone_placement {
+            config.free_tone_placement = value;
+        }
+        if let Some(value) = self.literal_foreign_initials {
+            config.literal_foreign_initials = value;
+        }
+        if let Some(value) = self.telex_bracket_shortcuts {
+            config.telex_bracket_shortcuts = value;
+        }
+        if let Some(value) = self.quick_telex {
+            config.quick_telex = value;
+        }
+        if let Some(value) = self.quick_consonants {
+            config.quick_consonants = value;
+        }
+        if let Some(value) = self.check_spelling {
+            config.check_spelling = value;
+        }
+        if let Some(value) = self.restore_invalid_words {
+            config.restore_invalid_words = value;
+        }
+        if let Some(value) = self.english_bypass {
+            config.english_bypass = value;
+        }
+        Ok(config)
+    }
+}
+
+fn input_method_name(method: &InputMethod) -> &'static str {
+    match method {
+        InputMethod::Telex => "telex",
+        InputMethod::Vni => "vni",
+        InputMethod::Viqr => "viqr",
+        InputMethod::SimpleTelex => "simple_telex",
+        InputMethod::MicrosoftVietnamese => "microsoft_vietnamese",
+        InputMethod::Custom(_) => "custom",
+    }
+}
+
+fn parse_input_method(value: &str) -> Option<InputMethod> {
+    Some(match value {
+        "telex" => InputMethod::Telex,
+        "vni" => InputMethod::Vni,
+        "viqr" => InputMethod::Viqr,
+        "simple_telex" => InputMethod::SimpleTelex,
+        "microsoft_vietnamese" => InputMethod::MicrosoftVietnamese,
+        _ => return None,
+    })
+}
+
+fn output_encoding_name(encoding: OutputEncoding) -> &'static str {
+    match encoding {
+        OutputEncoding::Unicode => "unicode",
+        OutputEncoding::CompositeUnicode => "composite_unicode",
+        OutputEncoding::Tcvn3 => "tcvn3",
+        OutputEncoding::Viscii => "viscii",
+        OutputEncoding::VniWindows => "vni_windows",
+        OutputEncoding::Viqr => "viqr",
+        OutputEncoding::HtmlEntity => "html_entity",
+        OutputEncoding::UnicodeEscape => "unicode_escape",
+        OutputEncoding::Cp1258 => "cp1258",
+    }
+}
+
+fn parse_output_encoding(value: &str) -> Option<OutputEncoding> {
+    Some(match value {
+        "unicode" => OutputEncoding::Unicode,
+        "composite_unicode" => OutputEncoding::CompositeUnicode,
+        "tcvn3" => OutputEncoding::Tcvn3,
+        "viscii" => OutputEncoding::Viscii,
+        "vni_windows" => OutputEncoding::VniWindows,
+        "viqr" => OutputEncoding::Viqr,
+        "html_entity" => OutputEncoding::HtmlEntity,
+        "unicode_escape" => OutputEncoding::UnicodeEscape,
+        "cp1258" => OutputEncoding::Cp1258,
+        _ => return None,
+    })
+}
+
+fn delete_count_unit_name(unit: DeleteCountUnit) -> &'static str {
+    match unit {
+        DeleteCountUnit::CodePoints => "code_points",
+        DeleteCountUnit::Utf16CodeUnits => "utf16_code_units",
+        DeleteCountUnit::Utf8Bytes => "utf8_bytes",
+        DeleteCountUnit::GraphemeClusters => "grapheme_clusters",
+    }
+}
+
+fn parse_delete_count_unit(value: &str) -> Option<DeleteCountUnit> {
+    Some(match value {
+        "code_points" => DeleteCountUnit::CodePoints,
+        "utf16_code_units" => DeleteCountUnit::Utf16CodeUnits,
+        "utf8_bytes" => DeleteCountUnit::Utf8Bytes,
+        "grapheme_clusters" => DeleteCountUnit::GraphemeClusters,
+        _ => return None,
+    })
+}
+
+fn tone_placement_name(placement: TonePlacement) -> &'static str {
+    match placement {
+        TonePlacement::Orthographic => "orthographic",
+        TonePlacement::NucleusOnly => "nucleus_only",
+    }
+}
+
+fn parse_tone_placement(value: &str) -> Option<TonePlacement> {
+    Some(match value {
+        "orthographic" => TonePlacement::Orthographic,
+        "nucleus_only" => TonePlacement::NucleusOnly,
+        _ => return None,
+    })
+}
+
+pub(crate) fn is_json_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+// ==================== EngineConfig Load/Save ====================
+
+impl EngineConfig {
+    pub fn from_toml_str(text: &str) -> Result<Self, SettingsError> {
+        let file: SettingsFile =
+            toml::from_str(text).map_err(|err| SettingsError::Parse(err.to_string()))?;
+        file.into_config()
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(&SettingsFile::from_config(self))
+            .expect("settings always serialize to TOML")
+    }
+
+    pub fn from_json_str(text: &str) -> Result<Self, SettingsError> {
+        let file: SettingsFile =
+            serde_json::from_str(text).map_err(|err| SettingsError::Parse(err.to_string()))?;
+        file.into_config()
+    }
+
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(&SettingsFile::from_config(self))
+            .expect("settings always serialize to JSON")
+    }
+
+    /// Reads a settings file. Files ending in `.json` are parsed as JSON, anything else as TOML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        if is_json_path(path) {
+            Self::from_json_str(&text)
+        } else {
+            Self::from_toml_str(&text)
+        }
+    }
+
+    /// Writes a settings file, choosing JSON or TOML from the extension like [`load`](Self::load).
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SettingsError> {
+        let path = path.as_ref();
+        let text = if is_json_path(path) {
+            self.to_json_string()
+        } else {
+            self.to_toml_string()
+        };
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! Removing diacritics from Vietnamese text, for search keys, file names and URL slugs.
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::diacritics::{escape_shape_preserving_tone, split_vowel_and_tone, VowelShape};
+
+/// Removes tone marks and keeps the letter shapes: `Tiếng Việt` → `Tiêng Viêt`.
+///
+/// Accepts precomposed or decomposed text and returns precomposed text.
+pub fn remove_tones(text: &str) -> String {
+    text.nfc().map(|ch| split_vowel_and_tone(ch).0).collect()
+}
+
+/// Removes every diacritic, including the stroke of đ: `Tiếng Việt Đường` → `Tieng Viet Duong`.
+///
+/// Accepts precomposed or decomposed text. Accents on non-Vietnamese letters (`ç`, `ü`) are
+/// removed too.
+pub fn remove_diacritics(text: &str) -> String {
+    text.nfc()
+        .map(|ch| unshape(split_vowel_and_tone(ch).0))
+        .collect::<String>()
+        .nfd()
+        .filter(|&ch| !is_combining_mark(ch))
+        .collect()
+}
+
+/// Lowercase ASCII slug for URLs and file names: `Tiếng Việt có dấu!` → `tieng-viet-co-dau`.
+///
+/// Runs of anything other than ASCII letters and digits become a single `-`, with none at
+/// either end.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut pending_separator = false;
+    for ch in remove_diacritics(text).chars() {
+        if ch.is_ascii_alphanumeric() {
+            if pending_separator && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_separator = false;
+            slug.push(ch.to_ascii_lowercase());
+        } else {
+            pending_separator = true;
+        }
+    }
+    slug
+}
+
+fn unshape(letter: char) -> char {
+    match letter {
+        'đ' => return 'd',
+        'Đ' => return 'D',
+        _ => {}
+    }
+    [VowelShape::Circumflex, VowelShape::Horn, VowelShape::Breve]
+        .into_iter()
+        .find_map(|shape| escape_shape_preserving_tone(letter, shape))
+        .unwrap_or(letter)
+}
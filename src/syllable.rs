@@ -0,0 +1,381 @@
+//! Structure of a Vietnamese syllable, shared by tone placement and [`Syllable::parse`], and
+//! the spelling rules checked by [`check_spelling`].
+
+use std::fmt;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::common::{is_vowel, lower_char};
+use crate::diacritics::split_vowel_and_tone;
+use crate::keymap::ToneMark;
+
+/// A Vietnamese syllable split into its parts.
+///
+/// `nghiêng` is `ngh` + `iê` + `ng`, `hoạch` is `h` + glide `o` + `a` + `ch` with a dot below,
+/// `quý` is `qu` + `y` with an acute. Letters keep their case; the tone is taken off the vowel
+/// that carried it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Syllable {
+    /// Initial consonants, possibly empty. The `u` of `qu` and the `i` of `gi` before another
+    /// vowel belong here.
+    pub onset: String,
+    /// The rounded glide before the nucleus: `o` in `hoa`, `u` in `tuy` or `thuở`.
+    pub glide: Option<char>,
+    /// The main vowel or diphthong: `a`, `ươ`, `iê`.
+    pub nucleus: String,
+    /// Final consonants or semivowel, possibly empty: `ng`, `ch`, `i`, `u`.
+    pub coda: String,
+    pub tone: Option<ToneMark>,
+}
+
+impl Syllable {
+    /// Splits one word into its parts.
+    ///
+    /// Accepts precomposed or decomposed text. Returns `None` unless `word` is all letters with
+    /// a single vowel cluster that Vietnamese spelling allows and at most one tone. Onset and
+    /// coda are not checked against the consonants Vietnamese allows.
+    ///
+    /// ```
+    /// use vitype_core::{Syllable, ToneMark};
+    ///
+    /// let syllable = Syllable::parse("nguyễn").unwrap();
+    /// assert_eq!(syllable.onset, "ng");
+    /// assert_eq!(syllable.glide, Some('u'));
+    /// assert_eq!(syllable.nucleus, "yê");
+    /// assert_eq!(syllable.coda, "n");
+    /// assert_eq!(syllable.tone, Some(ToneMark::Tilde));
+    /// ```
+    pub fn parse(word: &str) -> Option<Syllable> {
+        let mut letters = Vec::with_capacity(word.len());
+        let mut tone = None;
+        for ch in word.nfc() {
+            if !ch.is_alphabetic() {
+                return None;
+            }
+            let (letter, tone_key) = split_vowel_and_tone(ch);
+            if let Some(key) = tone_key {
+                if tone.is_some() {
+                    return None;
+                }
+                tone = ToneMark::from_tone_key(key);
+            }
+            letters.push(letter);
+        }
+
+        let vowels = vowel_indices(&letters);
+        let (&first, &last) = (vowels.first()?, vowels.last()?);
+        if last - first + 1 != vowels.len() || !is_valid_vowel_cluster(&letters[first..=last]) {
+            return None;
+        }
+
+        let mut cluster = &letters[first..=last];
+        let glide = (cluster.len() > 1 && is_glide(cluster[0], cluster[1])).then(|| cluster[0]);
+        if glide.is_some() {
+            cluster = &cluster[1..];
+        }
+        let nucleus_len = match cluster {
+            [_] => 1,
+            [first, second] if is_diphthong(*first, *second) => 2,
+            [_, _] => 1,
+            _ => 2,
+        };
+
+        Some(Syllable {
+            onset: letters[..first].iter().collect(),
+            glide,
+            nucleus: cluster[..nucleus_len].iter().collect(),
+            coda: cluster[nucleus_len..]
+                .iter()
+                .chain(&letters[last + 1..])
+                .collect(),
+            tone,
+        })
+    }
+}
+
+/// Why a word is not a correctly spelled Vietnamese syllable.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpellingError {
+    /// Not a single syllable: no vowel, several vowel clusters or tones, a vowel cluster
+    /// Vietnamese does not have, or something other than letters.
+    NotASyllable,
+    /// The initial consonants are not a Vietnamese onset (`fl`, `w`).
+    InvalidOnset(String),
+    /// The final letters are not a Vietnamese coda (`nk`, `s`).
+    InvalidCoda(String),
+    /// The onset is spelled differently before this vowel: `ngi` is `nghi`, `ke` is `ke` but
+    /// `ka` is `ca`. `expected` is lowercase.
+    OnsetSpelling { onset: String, expected: String },
+    /// Vowels and coda that do not go together (`tiê`, `mian`, `ăch`), as written.
+    InvalidRhyme(String),
+    /// Syllables ending in `p`, `t`, `c` or `ch` only take sắc or nặng.
+    ToneNotAllowed {
+        coda: String,
+        tone: Option<ToneMark>,
+    },
+}
+
+impl fmt::Display for SpellingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpellingError::NotASyllable => write!(f, "not a single Vietnamese syllable"),
+            SpellingError::InvalidOnset(onset) => {
+                write!(f, "`{}` is not a Vietnamese onset", onset)
+            }
+            SpellingError::InvalidCoda(coda) => write!(f, "`{}` is not a Vietnamese final", coda),
+            SpellingError::OnsetSpelling { onset, expected } => {
+                write!(f, "`{}` is spelled `{}` before this vowel", onset, expected)
+            }
+            SpellingError::InvalidRhyme(rhyme) => {
+                write!(f, "`{}` is not a Vietnamese rhyme", rhyme)
+            }
+            SpellingError::ToneNotAllowed { coda, tone } => match tone {
+                Some(tone) => write!(f, "final `{}` cannot take the {:?} tone", coda, tone),
+                None => write!(f, "final `{}` needs sắc or nặng", coda),
+            },
+        }
+    }
+}
+
+impl std::error::Error for SpellingError {}
+
+/// Parses `word` with [`Syllable::parse`] and checks it with [`Syllable::validate`].
+///
+/// ```
+/// use vitype_core::{check_spelling, SpellingError};
+///
+/// assert!(check_spelling("nghiêng").is_ok());
+/// assert!(matches!(
+///     check_spelling("ngi"),
+///     Err(SpellingError::OnsetSpelling { .. })
+/// ));
+/// assert!(matches!(
+///     check_spelling("càp"),
+///     Err(SpellingError::ToneNotAllowed { .. })
+/// ));
+/// ```
+pub fn check_spelling(word: &str) -> Result<Syllable, SpellingError> {
+    let syllable = Syllable::parse(word).ok_or(SpellingError::NotASyllable)?;
+    syllable.validate()?;
+    Ok(syllable)
+}
+
+impl Syllable {
+    /// Checks the syllable against Vietnamese spelling: the onset, the coda, how the onset is
+    /// spelled before the vowel (`c`/`k`, `g`/`gh`, `ng`/`ngh`), which vowels take which codas,
+    /// and that `p`/`t`/`c`/`ch` finals only take sắc or nặng.
+    pub fn validate(&self) -> Result<(), SpellingError> {
+        let onset = self.onset.to_lowercase();
+        let nucleus: Vec<char> = self.nucleus.chars().map(lower_char).collect();
+        let coda = self.coda.to_lowercase();
+
+        if !is_valid_onset(&onset) {
+            return Err(SpellingError::InvalidOnset(self.onset.clone()));
+        }
+        if !is_valid_coda(&coda) {
+            return Err(SpellingError::InvalidCoda(self.coda.clone()));
+        }
+
+        // The fields are public, so the syllable may not have come from `parse`.
+        let Some(&nucleus_start) = nucleus.first() else {
+            return Err(SpellingError::NotASyllable);
+        };
+        let first_vowel = self.glide.map(lower_char).unwrap_or(nucleus_start);
+        if let Some(expected) = expected_onset(&onset, first_vowel) {
+            return Err(SpellingError::OnsetSpelling {
+                onset: self.onset.clone(),
+                expected: expected.to_string(),
+            });
+        }
+
+        if !is_valid_rhyme(&nucleus, &coda) {
+            let mut rhyme: String = self.glide.into_iter().collect();
+            rhyme.push_str(&self.nucleus);
+            rhyme.push_str(&self.coda);
+            return Err(SpellingError::InvalidRhyme(rhyme));
+        }
+
+        let takes_only_stop_tones = matches!(coda.as_str(), "p" | "t" | "c" | "ch");
+        if takes_only_stop_tones && !matches!(self.tone, Some(ToneMark::Acute | ToneMark::DotBelow))
+        {
+            return Err(SpellingError::ToneNotAllowed {
+                coda: self.coda.clone(),
+                tone: self.tone,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn is_valid_onset(onset: &str) -> bool {
+    matches!(
+        onset,
+        "" | "b"
+            | "c"
+            | "ch"
+            | "d"
+            | "đ"
+            | "g"
+            | "gh"
+            | "gi"
+            | "h"
+            | "k"
+            | "kh"
+            | "l"
+            | "m"
+            | "n"
+            | "ng"
+            | "ngh"
+            | "nh"
+            | "p"
+            | "ph"
+            | "qu"
+            | "r"
+            | "s"
+            | "t"
+            | "th"
+            | "tr"
+            | "v"
+            | "x"
+    )
+}
+
+fn is_valid_coda(coda: &str) -> bool {
+    matches!(
+        coda,
+        "" | "c" | "ch" | "m" | "n" | "ng" | "nh" | "p" | "t" | "i" | "y" | "o" | "u"
+    )
+}
+
+/// The spelling `onset` must take before `vowel`, if it is not `onset` itself.
+fn expected_onset(onset: &str, vowel: char) -> Option<&'static str> {
+    let front = matches!(vowel, 'i' | 'e' | 'ê');
+    match onset {
+        "c" if front || vowel == 'y' => Some("k"),
+        "k" if !front && vowel != 'y' => Some("c"),
+        // "gì" is "gi" + "ì", so only e and ê need "gh".
+        "g" if matches!(vowel, 'e' | 'ê') => Some("gh"),
+        "gh" if !front => Some("g"),
+        "ng" if front => Some("ngh"),
+        "ngh" if !front => Some("ng"),
+        _ => None,
+    }
+}
+
+/// Whether the lowercase `nucleus` and `coda` go together.
+fn is_valid_rhyme(nucleus: &[char], coda: &str) -> bool {
+    match nucleus {
+        // iê, yê, uô and ươ are always followed by a coda; ia, ya, ua and ưa never are.
+        ['i' | 'y', 'ê'] | ['u', 'ô'] | ['ư', 'ơ'] => !coda.is_empty(),
+        ['i' | 'y', 'a'] | ['u' | 'ư', 'a'] => coda.is_empty(),
+        [_, _] => false,
+        [vowel] if matches!(coda, "ch" | "nh") => matches!(vowel, 'a' | 'ê' | 'i' | 'y'),
+        ['ă' | 'â'] => !coda.is_empty(),
+        _ => true,
+    }
+}
+
+/// Indices of the vowels of `word` that belong to its vowel cluster, in order: every vowel
+/// except the `u` of `qu`, and the `i` of `gi` when another vowel follows.
+pub(crate) fn vowel_indices(word: &[char]) -> Vec<usize> {
+    let mut indices = Vec::new();
+    let mut pending_gi_i: Option<usize> = None;
+
+    for (index, &ch) in word.iter().enumerate() {
+        if !is_vowel(ch) {
+            continue;
+        }
+        pending_gi_i = None;
+
+        let base = lower_char(split_vowel_and_tone(ch).0);
+        let previous = index
+            .checked_sub(1)
+            .map(|previous| lower_char(word[previous]));
+        if base == 'u' && previous == Some('q') {
+            continue;
+        }
+        if base == 'i' && previous == Some('g') {
+            pending_gi_i = Some(index);
+            continue;
+        }
+        indices.push(index);
+    }
+
+    indices.extend(pending_gi_i);
+    indices
+}
+
+/// Whether the untoned `vowels` (any case) form a vowel cluster Vietnamese spelling allows.
+pub(crate) fn is_valid_vowel_cluster(vowels: &[char]) -> bool {
+    if vowels.len() == 1 {
+        return true;
+    }
+    let cluster: Option<Vec<char>> = vowels
+        .iter()
+        .map(|&vowel| normalize_vowel_for_cluster(vowel))
+        .collect();
+    cluster.is_some_and(|cluster| is_valid_vowel_cluster_chars(&cluster))
+}
+
+/// Whether the untoned `vowel` is a glide before `next`: `oa`, `oă`, `oe`, `uy`, `uê`, `uâ`,
+/// `uơ`.
+pub(crate) fn is_glide(vowel: char, next: char) -> bool {
+    matches!(
+        (lower_char(vowel), lower_char(next)),
+        ('o', 'a' | 'ă' | 'e') | ('u', 'y' | 'ê' | 'â' | 'ơ')
+    )
+}
+
+fn is_diphthong(first: char, second: char) -> bool {
+    matches!(
+        (lower_char(first), lower_char(second)),
+        ('i' | 'y', 'a' | 'e' | 'ê') | ('u' | 'ư', 'a' | 'o' | 'ô' | 'ơ')
+    )
+}
+
+fn normalize_vowel_for_cluster(ch: char) -> Option<char> {
+    let lower = lower_char(ch);
+    Some(match lower {
+        'a' | 'e' | 'i' | 'o' | 'u' | 'y' => lower,
+        'ă' | 'â' => 'a',
+        'ê' => 'e',
+        'ô' | 'ơ' => 'o',
+        'ư' => 'u',
+        _ => return None,
+    })
+}
+
+#[allow(clippy::match_like_matches_macro)]
+fn is_valid_vowel_cluster_chars(cluster: &[char]) -> bool {
+    match cluster {
+        ['a', 'i']
+        | ['a', 'o']
+        | ['a', 'u']
+        | ['a', 'y']
+        | ['e', 'o']
+        | ['e', 'u']
+        | ['i', 'u']
+        | ['i', 'a']
+        | ['i', 'e']
+        | ['y', 'e']
+        | ['o', 'a']
+        | ['o', 'e']
+        | ['o', 'i']
+        | ['u', 'a']
+        | ['u', 'o']
+        | ['u', 'e']
+        | ['u', 'i']
+        | ['u', 'y']
+        | ['u', 'u']
+        | ['i', 'e', 'u']
+        | ['y', 'e', 'u']
+        | ['o', 'a', 'i']
+        | ['o', 'a', 'y']
+        | ['u', 'o', 'i']
+        | ['u', 'y', 'a']
+        | ['u', 'y', 'e']
+        | ['u', 'o', 'u'] => true,
+        _ => false,
+    }
+}
@@ -1,4 +1,4 @@
-use crate::common::{lower_char, KeyTransformAction, WTransformKind};
+use crate::common::{is_vowel, lower_char, InputMethod, KeyTransformAction, WTransformKind};
 use crate::diacritics::{
     apply_shape_preserving_tone, apply_telex_w_preserving_tone, escape_shape_preserving_tone,
     split_vowel_and_tone, VowelShape,
@@ -15,6 +15,91 @@ pub(super) fn is_telex_word_boundary(ch: char) -> bool {
     ch.is_ascii_whitespace() || ch.is_ascii_punctuation() || ch.is_ascii_digit()
 }
 
+fn is_foreign_consonant(ch: char) -> bool {
+    matches!(lower_char(ch), 'f' | 'j' | 'z')
+}
+
+/// Vowel typed directly by a bracket key when bracket shortcuts are enabled.
+pub(super) fn telex_bracket_vowel(ch: char) -> Option<char> {
+    Some(match ch {
+        '[' => 'ơ',
+        ']' => 'ư',
+        '{' => 'Ơ',
+        '}' => 'Ư',
+        _ => return None,
+    })
+}
+
+/// Second letter of the digraph typed by a doubled initial consonant in Quick Telex.
+fn quick_telex_digraph_tail(first_lower: char) -> Option<char> {
+    Some(match first_lower {
+        'c' => 'h',
+        'g' => 'i',
+        'k' => 'h',
+        'n' => 'g',
+        'p' => 'h',
+        'q' => 'u',
+        't' => 'h',
+        _ => return None,
+    })
+}
+
+/// Consonant typed by a word-initial `f`, `j` or `w` with quick consonants enabled.
+fn quick_start_consonant(key_lower: char) -> Option<&'static str> {
+    Some(match key_lower {
+        'f' => "ph",
+        'j' => "gi",
+        'w' => "qu",
+        _ => return None,
+    })
+}
+
+/// Final consonant typed by `g`, `h` or `k` right after the vowel cluster.
+fn quick_end_consonant(key_lower: char) -> Option<&'static str> {
+    Some(match key_lower {
+        'g' => "ng",
+        'h' => "nh",
+        'k' => "ch",
+        _ => return None,
+    })
+}
+
+/// Whether a syllable whose vowel cluster is `cluster` (lowercase base vowels) can end in `end`.
+fn is_valid_quick_end(cluster: &str, end: &str) -> bool {
+    match end {
+        "ng" => matches!(
+            cluster,
+            "a" | "ă"
+                | "â"
+                | "e"
+                | "o"
+                | "ô"
+                | "u"
+                | "ư"
+                | "oa"
+                | "oă"
+                | "uâ"
+                | "iê"
+                | "yê"
+                | "uô"
+                | "ươ"
+                | "oo"
+        ),
+        "nh" => matches!(cluster, "a" | "ê" | "i" | "y" | "oa" | "uê" | "uy"),
+        "ch" => matches!(cluster, "a" | "ê" | "i" | "oa" | "uê" | "uy"),
+        _ => false,
+    }
+}
+
+/// Uppercases `text` when `key` is uppercase.
+fn cased_like_key(text: &str, key: char) -> Vec<char> {
+    if key.is_uppercase() {
+        text.to_uppercase().chars().collect()
+    } else {
+        text.chars().collect()
+    }
+}
+
 fn telex_escape_char_for_key(ch: char, key_lower: char) -> Option<char> {
     match key_lower {
         'w' => escape_shape_preserving_tone(ch, VowelShape::Breve)
@@ -48,6 +133,23 @@ impl VitypeEngine {
             return None;
         }
 
+        // Handle bracket escapes ("[[" → "["): the bracket becomes plain punctuation.
+        if let Some(vowel) = telex_bracket_vowel(ch) {
+            if self.buffer.last() != Some(&vowel) {
+                return None;
+            }
+            // Drop both brackets from the raw keys so the committed word is restored without them.
+            self.buffer.pop();
+            self.raw_buffer
+                .truncate(self.raw_buffer.len().saturating_sub(2));
+            self.clear_last_transform_state();
+            self.end_word_with_boundary(ch);
+            return Some(KeyTransformAction {
+                delete_count: 1,
+                text: ch.to_string(),
+            });
+        }
+
         if ch_lower == 'w' {
             match self.last_w_transform_kind {
                 WTransformKind::Standalone => {
@@ -102,6 +204,21 @@ impl VitypeEngine {
             }
         }
 
+        // Handle quick consonant escapes ("ff" → "f", "agg" → "ag")
+        if let Some(action) = self.try_escape_quick_consonant(ch) {
+            return Some(action);
+        }
+
+        // Handle Quick Telex escapes ("ccc" → "cc")
+        if self.is_quick_telex_digraph(ch_lower) {
+            self.buffer[1] = ch;
+            self.clear_last_transform_and_suppress(ch_lower);
+            return Some(KeyTransformAction {
+                delete_count: 1,
+                text: ch.to_string(),
+            });
+        }
+
         if ch_lower == 'd' {
             if let Some(&last_char) = self.buffer.last() {
                 if last_char == 'đ' || last_char == 'Đ' {
@@ -168,6 +285,22 @@ impl VitypeEngine {
 
     pub(super) fn try_telex_consonant_transform(&mut self, ch: char) -> Option<KeyTransformAction> {
         let ch_lower = lower_char(ch);
+        if self.config.quick_telex {
+            if let Some(action) = self.try_quick_telex_digraph(ch) {
+                return Some(action);
+            }
+        }
+        if self.config.quick_consonants {
+            if let Some(action) = self.try_quick_start_consonant(ch) {
+                return Some(action);
+            }
+            if let Some(action) = self.try_quick_start_all_caps(ch) {
+                return Some(action);
+            }
+            if let Some(action) = self.try_quick_end_consonant(ch) {
+                return Some(action);
+            }
+        }
         if ch_lower != 'd' {
             return None;
         }
@@ -177,6 +310,26 @@ impl VitypeEngine {
     pub(super) fn try_telex_vowel_transform(&mut self, ch: char) -> Option<KeyTransformAction> {
         let ch_lower = lower_char(ch);
 
+        if self.config.telex_bracket_shortcuts {
+            if let Some(vowel) = telex_bracket_vowel(ch) {
+                self.buffer.pop();
+                self.buffer.push(vowel);
+                self.last_transform_key = Some(ch);
+                self.last_w_transform_kind = WTransformKind::None;
+
+                if self.config.auto_fix_tone {
+                    if let Some(action) = self.reposition_tone_if_needed(true, None) {
+                        return Some(action);
+                    }
+                }
+
+                return Some(KeyTransformAction {
+                    delete_count: 0,
+                    text: vowel.to_string(),
+                });
+            }
+        }
+
         if ch_lower == 'w' {
             if self.buffer.is_empty() {
                 return None;
@@ -228,6 +381,10 @@ impl VitypeEngine {
                 });
             }
 
+            if !self.has_standalone_w() {
+                return None;
+            }
+
             let replacement = if ch.is_uppercase() { 'Ư' } else { 'ư' };
             self.buffer.pop();
             self.buffer.push(replacement);
@@ -259,7 +416,7 @@ impl VitypeEngine {
                 self.last_transform_key = Some(ch);
                 self.last_w_transform_kind = WTransformKind::None;
 
-                if self.auto_fix_tone {
+                if self.config.auto_fix_tone {
                     if let Some(action) = self.reposition_tone_if_needed(false, Some(vowel_offset))
                     {
                         return Some(action);
@@ -286,8 +443,196 @@ impl VitypeEngine {
         self.apply_tone_mark_internal(ch_lower, ch)
     }
 
+    pub(super) fn is_telex_foreign_initial(&self, ch: char) -> bool {
+        if self.config.quick_consonants && quick_start_consonant(lower_char(ch)).is_some() {
+            return false;
+        }
+        is_foreign_consonant(ch) || (lower_char(ch) == 'w' && !self.has_standalone_w())
+    }
+
     // ==================== Telex Helper Methods ====================
 
+    /// Expands a doubled word-initial consonant into its digraph ("cc" → "ch").
+    fn try_quick_telex_digraph(&mut self, ch: char) -> Option<KeyTransformAction> {
+        let ch_lower = lower_char(ch);
+        if self.buffer.len() != 2 || lower_char(self.buffer[0]) != ch_lower {
+            return None;
+        }
+        let tail = quick_telex_digraph_tail(ch_lower)?;
+        let tail = if ch.is_uppercase() {
+            tail.to_ascii_uppercase()
+        } else {
+            tail
+        };
+
+        self.buffer[1] = tail;
+        self.last_transform_key = Some(ch);
+        self.last_w_transform_kind = WTransformKind::None;
+        Some(KeyTransformAction {
+            delete_count: 0,
+            text: tail.to_string(),
+        })
+    }
+
+    /// Expands a word-initial `f`/`j`/`w` into ph/gi/qu.
+    fn try_quick_start_consonant(&mut self, ch: char) -> Option<KeyTransformAction> {
+        if self.buffer.len() != 1 {
+            return None;
+        }
+        let consonant = quick_start_consonant(lower_char(ch))?;
+        // Only the first letter follows the key until the next key shows whether the word is
+        // all caps: "F" → "Ph", see `try_quick_start_all_caps`.
+        let mut chars: Vec<char> = consonant.chars().collect();
+        if ch.is_uppercase() {
+            chars[0] = chars[0].to_ascii_uppercase();
+        }
+
+        self.buffer.clear();
+        self.buffer.extend(&chars);
+        self.last_transform_key = Some(ch);
+        self.last_w_transform_kind = WTransformKind::None;
+        Some(KeyTransformAction {
+            delete_count: 0,
+            text: chars.into_iter().collect(),
+        })
+    }
+
+    /// Uppercases the tail of a quick start consonant when the second key of the word shows
+    /// it is all caps ("FA" → "PHA"), then applies the key and rewrites the word from the tail.
+    fn try_quick_start_all_caps(&mut self, ch: char) -> Option<KeyTransformAction> {
+        let &[key, second] = self.word_keys.as_slice() else {
+            return None;
+        };
+        if !key.is_uppercase()
+            || !ch.is_uppercase()
+            || second != ch
+            || self.last_transform_key != Some(key)
+            || lower_char(ch) == lower_char(key)
+        {
+            return None;
+        }
+        let consonant = quick_start_consonant(lower_char(key))?;
+        let shown = cased_like_key(consonant, key);
+        let tail = *shown.get(1)?;
+        if self.buffer.len() != 3
+            || self.buffer[0] != shown[0]
+            || self.buffer[1] != lower_char(tail)
+        {
+            return None;
+        }
+
+        self.buffer[1] = tail;
+        self.clear_last_transform_state();
+        // The key may still be a vowel or tone key ("JF" → "GÌ"); the rewrite covers either.
+        if self.try_telex_vowel_transform(ch).is_none() {
+            let _ = self.try_telex_tone_mark(ch);
+        }
+        Some(KeyTransformAction {
+            delete_count: 1,
+            text: self.buffer_string_from(1),
+        })
+    }
+
+    /// Expands `g`/`h`/`k` typed right after the vowel cluster into ng/nh/ch when the
+    /// syllable stays valid, moving the tone if the final consonant changes its position.
+    fn try_quick_end_consonant(&mut self, ch: char) -> Option<KeyTransformAction> {
+        let consonant = quick_end_consonant(lower_char(ch))?;
+        let key_index = self.buffer.len().checked_sub(1)?;
+        let cluster = self.trailing_vowel_cluster(key_index)?;
+        if !is_valid_quick_end(&cluster, consonant) {
+            return None;
+        }
+
+        let chars = cased_like_key(consonant, ch);
+        // Place the first letter as an unshown key so tone repositioning sees a final consonant.
+        self.buffer[key_index] = chars[0];
+        let mut action = if self.config.auto_fix_tone {
+            self.reposition_tone_if_needed(true, None)
+        } else {
+            None
+        }
+        .unwrap_or(KeyTransformAction {
+            delete_count: 0,
+            text: chars[0].to_string(),
+        });
+        self.buffer.push(chars[1]);
+        action.text.push(chars[1]);
+
+        self.last_transform_key = Some(ch);
+        self.last_w_transform_kind = WTransformKind::None;
+        Some(action)
+    }
+
+    fn try_escape_quick_consonant(&mut self, ch: char) -> Option<KeyTransformAction> {
+        if !self.config.quick_consonants {
+            return None;
+        }
+        let key_lower = lower_char(ch);
+        let consonant = quick_start_consonant(key_lower).or(quick_end_consonant(key_lower))?;
+        let expanded: Vec<char> = self
+            .buffer
+            .iter()
+            .rev()
+            .take(2)
+            .rev()
+            .map(|&c| lower_char(c))
+            .collect();
+        if expanded.iter().copied().ne(consonant.chars()) {
+            return None;
+        }
+        if quick_start_consonant(key_lower).is_some() && self.buffer.len() != 2 {
+            return None;
+        }
+
+        let first = self.buffer[self.buffer.len() - 2];
+        let original = if first.is_uppercase() {
+            ch.to_ascii_uppercase()
+        } else {
+            key_lower
+        };
+        self.buffer.truncate(self.buffer.len() - 2);
+        self.buffer.push(original);
+        self.clear_last_transform_and_suppress(key_lower);
+        Some(KeyTransformAction {
+            delete_count: 2,
+            text: original.to_string(),
+        })
+    }
+
+    /// Lowercase base vowels of the effective vowel cluster that ends right before `end`.
+    fn trailing_vowel_cluster(&self, end: usize) -> Option<String> {
+        if end == 0 || !is_vowel(self.buffer[end - 1]) {
+            return None;
+        }
+        let mut indices = Vec::new();
+        self.for_each_effective_vowel_index(end, |index| {
+            indices.push(index);
+            true
+        });
+        let last = *indices.last()?;
+        if last != end - 1 || indices.windows(2).any(|pair| pair[1] != pair[0] + 1) {
+            return None;
+        }
+        Some(
+            indices
+                .iter()
+                .map(|&index| lower_char(self.get_base_vowel(self.buffer[index])))
+                .collect(),
+        )
+    }
+
+    /// Whether the word is exactly a digraph just produced by Quick Telex from `key_lower`.
+    fn is_quick_telex_digraph(&self, key_lower: char) -> bool {
+        self.config.quick_telex
+            && self.buffer.len() == 2
+            && lower_char(self.buffer[0]) == key_lower
+            && quick_telex_digraph_tail(key_lower) == Some(lower_char(self.buffer[1]))
+    }
+
+    fn has_standalone_w(&self) -> bool {
+        self.config.input_method != InputMethod::SimpleTelex
+    }
+
     fn find_last_untransformable_vowel(
         &self,
         key_lower: char,
@@ -0,0 +1,83 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::type_keys;
+use crate::{
+    DeleteCountUnit, EngineConfig, InputMethod, OutputEncoding, TonePlacement, VitypeEngine,
+};
+
+#[test]
+fn testDefaultConfigMatchesNewEngine() {
+    let engine = VitypeEngine::new();
+    assert_eq!(engine.config(), EngineConfig::default());
+    assert_eq!(EngineConfig::builder().build(), EngineConfig::default());
+}
+
+#[test]
+fn testBuilderSetsEveryOption() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .output_encoding(OutputEncoding::CompositeUnicode)
+        .delete_count_unit(DeleteCountUnit::Utf16CodeUnits)
+        .tone_placement(TonePlacement::NucleusOnly)
+        .auto_fix_tone(false)
+        .free_tone_placement(true)
+        .literal_foreign_initials(true)
+        .telex_bracket_shortcuts(true)
+        .quick_telex(true)
+        .quick_consonants(true)
+        .check_spelling(true)
+        .restore_invalid_words(true)
+        .english_bypass(true)
+        .build();
+
+    assert_eq!(config.input_method, InputMethod::Vni);
+    assert_eq!(config.output_encoding, OutputEncoding::CompositeUnicode);
+    assert_eq!(config.delete_count_unit, DeleteCountUnit::Utf16CodeUnits);
+    assert_eq!(config.tone_placement, TonePlacement::NucleusOnly);
+    assert!(!config.auto_fix_tone);
+    assert!(config.free_tone_placement);
+    assert!(config.literal_foreign_initials);
+    assert!(config.telex_bracket_shortcuts);
+    assert!(config.quick_telex);
+    assert!(config.quick_consonants);
+    assert!(config.check_spelling);
+    assert!(config.restore_invalid_words);
+    assert!(config.english_bypass);
+
+    let engine = VitypeEngine::with_config(config.clone());
+    assert_eq!(engine.config(), config);
+}
+
+#[test]
+fn testWithConfigUsesInputMethod() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .build();
+    let mut engine = VitypeEngine::with_config(config);
+    assert_eq!(type_keys(&mut engine, "vie65t"), "việt");
+}
+
+#[test]
+fn testApplyConfigReplacesAllSettings() {
+    let mut engine = VitypeEngine::new();
+    let config = EngineConfig::builder()
+        .tone_placement(TonePlacement::NucleusOnly)
+        .free_tone_placement(true)
+        .build();
+    engine.apply_config(config.clone());
+
+    assert_eq!(engine.config(), config);
+    assert_eq!(engine.tone_placement(), TonePlacement::NucleusOnly);
+    assert!(engine.free_tone_placement());
+    assert_eq!(type_keys(&mut engine, "hoaf"), "hoà");
+}
+
+#[test]
+fn testBackspaceRebuildKeepsFreeTonePlacement() {
+    let config = EngineConfig::builder().free_tone_placement(true).build();
+    let mut engine = VitypeEngine::with_config(config);
+
+    // Backspace rebuilds the word from raw keys; the rebuilt state must still allow the
+    // free-placed tone so that the repeated `s` escapes it.
+    assert_eq!(type_keys(&mut engine, "aoisq<s"), "aois");
+}
@@ -0,0 +1,169 @@
+#![allow(non_snake_case)]
+
+use crate::ffi::{vitype_bytes_free, vitype_convert_text, vitype_detect_encoding};
+use crate::{convert_text, decode_text, detect_encoding, encode_text, OutputEncoding};
+
+const SAMPLE: &str = "Tiếng Việt có sáu thanh điệu: ngang, huyền, sắc, hỏi, ngã và nặng. \
+Người ta thường viết chữ quốc ngữ bằng bảng mã Unicode.";
+
+const ALL_ENCODINGS: [OutputEncoding; 9] = [
+    OutputEncoding::Unicode,
+    OutputEncoding::CompositeUnicode,
+    OutputEncoding::Tcvn3,
+    OutputEncoding::Viscii,
+    OutputEncoding::VniWindows,
+    OutputEncoding::Viqr,
+    OutputEncoding::HtmlEntity,
+    OutputEncoding::UnicodeEscape,
+    OutputEncoding::Cp1258,
+];
+
+// ==================== Round Trips ====================
+
+#[test]
+fn testRoundTripThroughEveryEncoding() {
+    // Lowercase only: TCVN3 shares codes between upper- and lowercase toned letters.
+    let text = SAMPLE.to_lowercase();
+    for encoding in ALL_ENCODINGS {
+        let encoded = encode_text(&text, encoding);
+        assert_eq!(decode_text(&encoded, encoding), text, "{encoding:?}");
+    }
+}
+
+#[test]
+fn testRoundTripKeepsUppercaseExceptInTcvn3() {
+    for encoding in ALL_ENCODINGS {
+        if encoding == OutputEncoding::Tcvn3 {
+            continue;
+        }
+        let encoded = encode_text(SAMPLE, encoding);
+        assert_eq!(decode_text(&encoded, encoding), SAMPLE, "{encoding:?}");
+    }
+
+    let tcvn3 = encode_text("Việt NAM ĐẸP", OutputEncoding::Tcvn3);
+    assert_eq!(decode_text(&tcvn3, OutputEncoding::Tcvn3), "Việt NAM ĐẹP");
+}
+
+#[test]
+fn testConvertBetweenLegacyEncodings() {
+    let vni = encode_text(SAMPLE, OutputEncoding::VniWindows);
+    let viscii = convert_text(&vni, OutputEncoding::VniWindows, OutputEncoding::Viscii);
+    assert_eq!(viscii, encode_text(SAMPLE, OutputEncoding::Viscii));
+    let unicode = convert_text(&viscii, OutputEncoding::Viscii, OutputEncoding::Unicode);
+    assert_eq!(unicode, SAMPLE.as_bytes());
+}
+
+#[test]
+fn testDecodeNormalizesToPrecomposed() {
+    let composite = encode_text("Việt", OutputEncoding::CompositeUnicode);
+    assert_eq!(composite, "Vie\u{323}\u{302}t".as_bytes());
+    assert_eq!(
+        decode_text(&composite, OutputEncoding::CompositeUnicode),
+        "Việt"
+    );
+}
+
+#[test]
+fn testUnrepresentableCharactersBecomeQuestionMarks() {
+    assert_eq!(encode_text("a→b", OutputEncoding::Viscii), b"a?b");
+    // Latin-1 letters outside the table must not be written as their own code points.
+    assert_eq!(encode_text("ü©", OutputEncoding::Tcvn3), b"??");
+    for encoding in [
+        OutputEncoding::Tcvn3,
+        OutputEncoding::Viscii,
+        OutputEncoding::VniWindows,
+        OutputEncoding::Cp1258,
+    ] {
+        let encoded = encode_text("việt ü© ñ", encoding);
+        assert_eq!(decode_text(&encoded, encoding), "việt ?? ?", "{encoding:?}");
+    }
+}
+
+// ==================== Escaping ====================
+
+#[test]
+fn testViqrEscapesAmbiguousText() {
+    let encoded = encode_text("bà. add C:\\", OutputEncoding::Viqr);
+    assert_eq!(encoded, b"ba`\\. ad\\d C:\\\\");
+    assert_eq!(decode_text(&encoded, OutputEncoding::Viqr), "bà. add C:\\");
+}
+
+#[test]
+fn testViqrDecodesShapesAndTones() {
+    assert_eq!(
+        decode_text(b"Tie^'ng Vie^.t, ddu+o+`ng", OutputEncoding::Viqr),
+        "Tiếng Việt, đường"
+    );
+}
+
+#[test]
+fn testHtmlEntityEscapesAmpersand() {
+    let encoded = encode_text("Á & &#7879;", OutputEncoding::HtmlEntity);
+    assert_eq!(encoded, b"&#193; &amp; &amp;#7879;");
+    assert_eq!(
+        decode_text(&encoded, OutputEncoding::HtmlEntity),
+        "Á & &#7879;"
+    );
+    assert_eq!(
+        decode_text(b"&#x1EC7; &lt;b&gt;", OutputEncoding::HtmlEntity),
+        "ệ <b>"
+    );
+}
+
+#[test]
+fn testUnicodeEscapeHandlesSurrogatePairs() {
+    let encoded = encode_text("ệ😀\\u", OutputEncoding::UnicodeEscape);
+    assert_eq!(encoded, b"\\u1EC7\\uD83D\\uDE00\\\\u");
+    assert_eq!(
+        decode_text(&encoded, OutputEncoding::UnicodeEscape),
+        "ệ😀\\u"
+    );
+}
+
+// ==================== Detection ====================
+
+#[test]
+fn testDetectEncodingOfSampleInEveryEncoding() {
+    for encoding in ALL_ENCODINGS {
+        let encoded = encode_text(SAMPLE, encoding);
+        assert_eq!(detect_encoding(&encoded), encoding, "{encoding:?}");
+    }
+}
+
+#[test]
+fn testDetectPlainAsciiAsUnicode() {
+    assert_eq!(
+        detect_encoding(b"Hello world, nothing to see here."),
+        OutputEncoding::Unicode
+    );
+    assert_eq!(detect_encoding(b""), OutputEncoding::Unicode);
+}
+
+// ==================== FFI ====================
+
+#[test]
+fn testFfiConvertAndDetect() {
+    let vni = encode_text(SAMPLE, OutputEncoding::VniWindows);
+    assert_eq!(
+        vitype_detect_encoding(vni.as_ptr(), vni.len()),
+        OutputEncoding::VniWindows as i32
+    );
+
+    let bytes = vitype_convert_text(
+        vni.as_ptr(),
+        vni.len(),
+        OutputEncoding::VniWindows as i32,
+        OutputEncoding::Unicode as i32,
+    );
+    let converted = unsafe { std::slice::from_raw_parts(bytes.data, bytes.len) };
+    assert_eq!(converted, SAMPLE.as_bytes());
+    vitype_bytes_free(bytes);
+}
+
+#[test]
+fn testFfiConvertEmptyInput() {
+    let bytes = vitype_convert_text(std::ptr::null(), 0, 0, 2);
+    assert_eq!(bytes.len, 0);
+    vitype_bytes_free(bytes);
+    assert_eq!(vitype_detect_encoding(std::ptr::null(), 0), 0);
+}
@@ -0,0 +1,145 @@
+#![allow(non_snake_case)]
+
+use std::ffi::CString;
+
+use super::test_helpers::{action, apply_input, apply_input_with_config, temp_path, type_keys};
+use crate::ffi::{
+    vitype_engine_free, vitype_engine_load_english_words, vitype_engine_new,
+    vitype_engine_set_english_bypass,
+};
+use crate::{check_spelling, transliterate, EngineConfig, InputMethod, VitypeEngine};
+
+fn bypassing(input: &str) -> String {
+    apply_input_with_config(input, EngineConfig::builder().english_bypass(true).build())
+}
+
+#[test]
+fn testEnglishWordsAreTypedAsIs() {
+    assert_eq!(bypassing("mass "), "mass ");
+    assert_eq!(bypassing("boss"), "boss");
+    assert_eq!(bypassing("week"), "week");
+    assert_eq!(bypassing("offer "), "offer ");
+    assert_eq!(bypassing("class "), "class ");
+}
+
+#[test]
+fn testVietnameseIsUnaffected() {
+    assert_eq!(bypassing("tieengs vieetj "), "tiếng việt ");
+    assert_eq!(bypassing("mas "), "má ");
+}
+
+#[test]
+fn testMatchRewritesTheWord() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().english_bypass(true).build());
+    assert_eq!(type_keys(&mut engine, "mas"), "má");
+    assert_eq!(engine.process("s"), Some(action(2, "mass")));
+    // The word is foreign now, so later keys are typed as-is.
+    assert_eq!(engine.process("e"), None);
+    assert_eq!(engine.process("s"), None);
+}
+
+#[test]
+fn testWordRestoredWithBackspaceIsChecked() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().english_bypass(true).build());
+    assert_eq!(type_keys(&mut engine, "mas "), "má ");
+    engine.delete_last_character();
+    assert_eq!(engine.process("s"), Some(action(2, "mass")));
+    assert_eq!(engine.process(" "), None);
+}
+
+#[test]
+fn testBypassIsOffByDefault() {
+    assert_eq!(apply_input("mass "), "mas ");
+    assert!(!VitypeEngine::new().english_bypass());
+}
+
+#[test]
+fn testUserWordsAreTypedAsIs() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().english_bypass(true).build());
+    engine.add_english_words(["Docker"]);
+    assert_eq!(type_keys(&mut engine, "docker "), "docker ");
+    assert_eq!(type_keys(&mut engine, "DOCKER "), "DOCKER ");
+    assert_eq!(type_keys(&mut engine, "docs "), "dóc ");
+}
+
+#[test]
+fn testWordsThatTypeASyllableNeedAUserWord() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().english_bypass(true).build());
+    // "mix" types "mĩ", so the built-in list leaves it out.
+    assert_eq!(type_keys(&mut engine, "mix "), "mĩ ");
+    engine.add_english_words(["mix"]);
+    assert_eq!(type_keys(&mut engine, "mix "), "mix ");
+}
+
+#[test]
+fn testUserWordsSurviveReset() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().english_bypass(true).build());
+    engine.add_english_words(["dior"]);
+    engine.reset();
+    assert_eq!(type_keys(&mut engine, "dior "), "dior ");
+}
+
+#[test]
+fn testLoadEnglishWords() {
+    let path = temp_path("english_words.txt");
+    std::fs::write(&path, "# brands\n\nDior\n  docker  \n").unwrap();
+
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().english_bypass(true).build());
+    engine.load_english_words(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(type_keys(&mut engine, "dior docker "), "dior docker ");
+    assert!(engine.load_english_words(&path).is_err());
+}
+
+#[test]
+fn testBuiltInWordsAreNotVietnamese() {
+    let words = include_str!("../english_words.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    for word in words {
+        assert_eq!(word, word.to_lowercase());
+        let typed = transliterate(word, InputMethod::Telex, EngineConfig::default());
+        assert_ne!(typed, word, "{word} needs no bypass");
+        assert!(check_spelling(&typed).is_err(), "{word} types {typed}");
+    }
+}
+
+#[test]
+fn testSettingsKeepBypass() {
+    let path = temp_path("english_settings.toml");
+    EngineConfig::builder()
+        .english_bypass(true)
+        .build()
+        .save(&path)
+        .unwrap();
+    let loaded = EngineConfig::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(loaded.english_bypass);
+}
+
+#[test]
+fn testFfiBypassAndWordFile() {
+    let path = temp_path("english_ffi.txt");
+    std::fs::write(&path, "docker\n").unwrap();
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+    let engine = vitype_engine_new();
+    vitype_engine_set_english_bypass(engine, true);
+    assert_eq!(vitype_engine_load_english_words(engine, c_path.as_ptr()), 0);
+    assert_eq!(
+        vitype_engine_load_english_words(engine, std::ptr::null()),
+        -1
+    );
+    let engine_ref = unsafe { &mut *engine };
+    assert!(engine_ref.english_bypass());
+    assert_eq!(type_keys(engine_ref, "docker mass "), "docker mass ");
+    vitype_engine_free(engine);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,212 @@
+#![allow(non_snake_case)]
+
+use std::ffi::CString;
+
+use super::test_helpers::{action, apply_input, temp_path, type_keys};
+use crate::ffi::{
+    vitype_engine_add_exclusion, vitype_engine_clear_exclusions, vitype_engine_free,
+    vitype_engine_load_exclusions, vitype_engine_new, vitype_engine_remove_exclusion,
+    vitype_engine_save_exclusions,
+};
+use crate::{ExclusionKind, ExclusionList, ExclusionPattern, SettingsError, VitypeEngine};
+
+fn team_engine() -> VitypeEngine {
+    let mut engine = VitypeEngine::new();
+    engine.add_exclusion(ExclusionPattern::exact("async"));
+    engine.add_exclusion(ExclusionPattern::exact("goto"));
+    engine.add_exclusion(ExclusionPattern::prefix("kube"));
+    engine.add_exclusion(ExclusionPattern::exact("ssh"));
+    engine
+}
+
+#[test]
+fn testExcludedWordsAreTypedAsIs() {
+    let mut engine = team_engine();
+    assert_ne!(apply_input("async goto "), "async goto ");
+    assert_eq!(
+        type_keys(&mut engine, "async goto kubectl kubectx ssh "),
+        "async goto kubectl kubectx ssh "
+    );
+}
+
+#[test]
+fn testOtherWordsAreTransformed() {
+    let mut engine = team_engine();
+    assert_eq!(type_keys(&mut engine, "tieengs vieetj "), "tiếng việt ");
+    assert_eq!(type_keys(&mut engine, "kus "), "kú ");
+}
+
+#[test]
+fn testMatchRewritesTheWord() {
+    let mut engine = team_engine();
+    // Whole words are restored when they end.
+    assert_eq!(type_keys(&mut engine, "async"), "aýnc");
+    assert_eq!(engine.process(" "), Some(action(4, "async ")));
+    assert_eq!(type_keys(&mut engine, "goto"), "gôt");
+    assert_eq!(engine.process(" "), Some(action(3, "goto ")));
+    // Keys that were never transformed need no action.
+    assert_eq!(type_keys(&mut engine, "ssh"), "ssh");
+    assert_eq!(engine.process(" "), None);
+
+    // Prefixes switch the word as soon as they match.
+    let mut engine = VitypeEngine::new();
+    engine.add_exclusion(ExclusionPattern::prefix("asy"));
+    assert_eq!(type_keys(&mut engine, "as"), "á");
+    assert_eq!(engine.process("y"), Some(action(1, "asy")));
+}
+
+#[test]
+fn testExactWordsDoNotStopLongerWords() {
+    let mut engine = VitypeEngine::new();
+    engine.add_exclusion(ExclusionPattern::exact("ma"));
+    assert_eq!(type_keys(&mut engine, "mas "), "má ");
+    assert_eq!(type_keys(&mut engine, "maf "), "mà ");
+}
+
+#[test]
+fn testCaseOptions() {
+    let mut engine = VitypeEngine::new();
+    engine.add_exclusion(ExclusionPattern::exact("Async").case_sensitive(true));
+    engine.add_exclusion(ExclusionPattern::exact("goto"));
+    assert_eq!(type_keys(&mut engine, "Async GOTO "), "Async GOTO ");
+    assert_ne!(type_keys(&mut engine, "async "), "async ");
+
+    assert!(ExclusionPattern::prefix("kube").matches("KUBECTL"));
+    assert!(!ExclusionPattern::prefix("kube")
+        .case_sensitive(true)
+        .matches("KUBECTL"));
+}
+
+#[test]
+fn testAddAndRemove() {
+    let mut list = ExclusionList::new();
+    assert!(list.add(ExclusionPattern::exact("goto")));
+    assert!(!list.add(ExclusionPattern::exact("goto")));
+    assert!(list.add(ExclusionPattern::prefix("goto")));
+    assert!(!list.add(ExclusionPattern::exact("")));
+    assert_eq!(list.patterns().len(), 2);
+    assert_eq!(list.patterns()[1].kind(), ExclusionKind::Prefix);
+
+    assert!(list.remove(&ExclusionPattern::exact("goto")));
+    assert!(!list.remove(&ExclusionPattern::exact("goto")));
+    assert_eq!(list.patterns(), [ExclusionPattern::prefix("goto")]);
+
+    let mut engine = team_engine();
+    assert!(engine.remove_exclusion(&ExclusionPattern::exact("goto")));
+    assert_ne!(type_keys(&mut engine, "goto "), "goto ");
+}
+
+#[test]
+fn testExclusionsSurviveReset() {
+    let mut engine = team_engine();
+    engine.reset();
+    assert_eq!(type_keys(&mut engine, "goto "), "goto ");
+}
+
+#[test]
+fn testFileRoundTrips() {
+    let mut list = ExclusionList::new();
+    list.add(ExclusionPattern::exact("goto"));
+    list.add(ExclusionPattern::prefix("kube").case_sensitive(true));
+
+    assert_eq!(
+        ExclusionList::from_toml_str(&list.to_toml_string()).unwrap(),
+        list
+    );
+    assert_eq!(
+        ExclusionList::from_json_str(&list.to_json_string()).unwrap(),
+        list
+    );
+
+    let parsed = ExclusionList::from_toml_str(
+        "version = 1\n\n[[exclude]]\nword = \"goto\"\n\n[[exclude]]\nprefix = \"kube\"\ncase_sensitive = true\n",
+    )
+    .unwrap();
+    assert_eq!(parsed, list);
+}
+
+#[test]
+fn testFileValidationErrors() {
+    assert!(matches!(
+        ExclusionList::from_toml_str("[[exclude]]\nword = \"goto\"\n"),
+        Err(SettingsError::MissingVersion)
+    ));
+    assert!(matches!(
+        ExclusionList::from_toml_str("version = 99\n"),
+        Err(SettingsError::UnsupportedVersion(99))
+    ));
+    match ExclusionList::from_toml_str(
+        "version = 1\n[[exclude]]\nword = \"goto\"\nprefix = \"kube\"\n",
+    ) {
+        Err(SettingsError::InvalidValue { key, value }) => {
+            assert_eq!(key, "exclude");
+            assert_eq!(value, "goto");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(matches!(
+        ExclusionList::from_toml_str("version = 1\n[[exclude]]\ncase_sensitive = true\n"),
+        Err(SettingsError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn testEngineLoadAndSave() {
+    let path = temp_path("exclusions.toml");
+    team_engine().save_exclusions(&path).unwrap();
+
+    let mut engine = VitypeEngine::new();
+    engine.load_exclusions(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(engine.exclusions(), team_engine().exclusions());
+    assert_eq!(type_keys(&mut engine, "goto "), "goto ");
+}
+
+#[test]
+fn testFfiExclusions() {
+    let path = temp_path("exclusions_ffi.json");
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+    let goto = CString::new("goto").unwrap();
+
+    let engine = vitype_engine_new();
+    assert!(vitype_engine_add_exclusion(engine, goto.as_ptr(), 0, false));
+    assert!(!vitype_engine_add_exclusion(
+        engine,
+        goto.as_ptr(),
+        0,
+        false
+    ));
+    assert!(!vitype_engine_add_exclusion(
+        engine,
+        goto.as_ptr(),
+        7,
+        false
+    ));
+    assert!(!vitype_engine_add_exclusion(
+        engine,
+        std::ptr::null(),
+        0,
+        false
+    ));
+    assert_eq!(type_keys(unsafe { &mut *engine }, "goto "), "goto ");
+
+    assert_eq!(vitype_engine_save_exclusions(engine, c_path.as_ptr()), 0);
+    vitype_engine_clear_exclusions(engine);
+    assert!(unsafe { &*engine }.exclusions().is_empty());
+    assert_eq!(vitype_engine_load_exclusions(engine, c_path.as_ptr()), 0);
+    assert!(vitype_engine_remove_exclusion(
+        engine,
+        goto.as_ptr(),
+        0,
+        false
+    ));
+    assert!(!vitype_engine_remove_exclusion(
+        engine,
+        goto.as_ptr(),
+        1,
+        false
+    ));
+    assert_eq!(vitype_engine_load_exclusions(engine, std::ptr::null()), -1);
+    vitype_engine_free(engine);
+    std::fs::remove_file(&path).unwrap();
+}
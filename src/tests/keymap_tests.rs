@@ -0,0 +1,224 @@
+#![allow(non_snake_case)]
+
+use std::ffi::CString;
+use std::sync::Arc;
+
+use super::test_helpers::{apply_input_with_config, apply_viqr_input, apply_vni_input, temp_path};
+use crate::ffi::{vitype_engine_free, vitype_engine_load_keymap, vitype_engine_new};
+use crate::{EngineConfig, InputMethod, KeyAction, Keymap, SettingsError, ToneMark};
+
+const VNI_KEYMAP: &str = r#"
+version = 1
+name = "VNI (custom)"
+
+[keys]
+"1" = "tone_s"
+"2" = "tone_f"
+"3" = "tone_r"
+"4" = "tone_x"
+"5" = "tone_j"
+"0" = "remove_tone"
+"6" = "circumflex"
+"7" = "horn"
+"8" = "breve"
+"9" = "d_stroke"
+
+[boundaries]
+digits = false
+"#;
+
+fn apply_keymap_input(input: &str, keymap: Keymap) -> String {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Custom(Arc::new(keymap)))
+        .build();
+    apply_input_with_config(input, config)
+}
+
+fn viqr_like_keymap() -> Keymap {
+    Keymap::builder("VIQR subset")
+        .key('\'', KeyAction::Tone(ToneMark::Acute))
+        .key('`', KeyAction::Tone(ToneMark::Grave))
+        .key('.', KeyAction::Tone(ToneMark::DotBelow))
+        .key('^', KeyAction::Circumflex)
+        .key('+', KeyAction::Horn)
+        .key('d', KeyAction::DStroke)
+        .literal_prefix(Some('\\'))
+        .build()
+}
+
+// MARK: - Behavior
+
+#[test]
+fn testVniKeymapMatchesBuiltInVni() {
+    let keymap = Keymap::from_toml_str(VNI_KEYMAP).unwrap();
+    for input in [
+        "Vie65t Nam",
+        "nguo77i",
+        "d9a6t nuo7c1",
+        "a66",
+        "to6i1",
+        "hoa2i",
+        "tie6ng1 vie65t",
+        "a11",
+        "d99",
+        "ba1o0",
+        "ha8m2",
+        "user123",
+    ] {
+        assert_eq!(
+            apply_keymap_input(input, keymap.clone()),
+            apply_vni_input(input),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn testBuiltInKeymapsMatchBuiltInMethods() {
+    for input in ["Vie65t Nam", "d9a6t nuo7c1", "a66", "ba1o0", "user123"] {
+        assert_eq!(
+            apply_keymap_input(input, Keymap::vni()),
+            apply_vni_input(input),
+            "{}",
+            input
+        );
+    }
+    for input in ["Vie^.t Nam", "dda^.t nu+o+'c", "u++", "ba\\.", "d-a^u 123"] {
+        assert_eq!(
+            apply_keymap_input(input, Keymap::viqr()),
+            apply_viqr_input(input),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn testPunctuationKeysActLikeViqr() {
+    for input in [
+        "Vie^.t", "a'", "b'", "ba. ", "ba\\.", "dda^.t", "nu+o+'c", "u++",
+    ] {
+        assert_eq!(
+            apply_keymap_input(input, viqr_like_keymap()),
+            apply_viqr_input(input),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn testLetterKeysAreLiteralWhenTheyCannotApply() {
+    let keymap = Keymap::builder("letters")
+        .key('q', KeyAction::Tone(ToneMark::Acute))
+        .key('w', KeyAction::Horn)
+        .build();
+    assert_eq!(apply_keymap_input("q", keymap.clone()), "q");
+    assert_eq!(apply_keymap_input("tuwq", keymap.clone()), "tứ");
+    assert_eq!(apply_keymap_input("TUWQ", keymap.clone()), "TỨ");
+    assert_eq!(apply_keymap_input("tqw", keymap), "tqw");
+}
+
+#[test]
+fn testDoubleKeyEscapeCanBeDisabled() {
+    let keymap = Keymap::builder("no escape")
+        .key('6', KeyAction::Circumflex)
+        .digit_boundaries(false)
+        .double_key_escape(false)
+        .build();
+    assert_eq!(apply_keymap_input("a66", keymap), "â6");
+}
+
+#[test]
+fn testBoundaries() {
+    let keymap = Keymap::builder("boundaries")
+        .key('-', KeyAction::Literal)
+        .digit_boundaries(false)
+        .extra_boundaries("§")
+        .build();
+    assert!(keymap.is_word_boundary(' '));
+    assert!(keymap.is_word_boundary(','));
+    assert!(keymap.is_word_boundary('§'));
+    assert!(!keymap.is_word_boundary('-'));
+    assert!(!keymap.is_word_boundary('5'));
+    assert_eq!(apply_keymap_input("e-mail", keymap), "e-mail");
+}
+
+// MARK: - File Format
+
+#[test]
+fn testTomlAndJsonRoundTrip() {
+    let keymap = viqr_like_keymap();
+    assert_eq!(
+        Keymap::from_toml_str(&keymap.to_toml_string()).unwrap(),
+        keymap
+    );
+    assert_eq!(
+        Keymap::from_json_str(&keymap.to_json_string()).unwrap(),
+        keymap
+    );
+}
+
+#[test]
+fn testKeymapValidationErrors() {
+    assert!(matches!(
+        Keymap::from_toml_str("[keys]\n\"1\" = \"tone_s\"\n"),
+        Err(SettingsError::MissingVersion)
+    ));
+    match Keymap::from_toml_str("version = 1\n[keys]\n\"1\" = \"tone_q\"\n") {
+        Err(SettingsError::InvalidValue { key, value }) => {
+            assert_eq!(key, "keys");
+            assert_eq!(value, "tone_q");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match Keymap::from_toml_str("version = 1\n[keys]\n\"aa\" = \"circumflex\"\n") {
+        Err(SettingsError::InvalidValue { key, value }) => {
+            assert_eq!(key, "keys");
+            assert_eq!(value, "aa");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn testSettingsEmbedCustomKeymap() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Custom(Arc::new(viqr_like_keymap())))
+        .build();
+    let text = config.to_toml_string();
+    assert!(text.contains("input_method = \"custom\""));
+    assert_eq!(EngineConfig::from_toml_str(&text).unwrap(), config);
+    assert_eq!(
+        EngineConfig::from_json_str(&config.to_json_string()).unwrap(),
+        config
+    );
+
+    assert!(matches!(
+        EngineConfig::from_toml_str("version = 1\ninput_method = \"custom\"\n"),
+        Err(SettingsError::InvalidValue {
+            key: "input_method",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn testFfiLoadKeymap() {
+    let path = temp_path("keymap.toml");
+    std::fs::write(&path, VNI_KEYMAP).unwrap();
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+    let engine = vitype_engine_new();
+    assert_eq!(vitype_engine_load_keymap(engine, c_path.as_ptr()), 0);
+    match unsafe { (*engine).input_method() } {
+        InputMethod::Custom(keymap) => assert_eq!(keymap.name(), "VNI (custom)"),
+        other => panic!("unexpected input method: {:?}", other),
+    }
+
+    std::fs::write(&path, "version = 1\n[keys]\n\"6\" = \"hat\"\n").unwrap();
+    assert_eq!(vitype_engine_load_keymap(engine, c_path.as_ptr()), 4);
+    vitype_engine_free(engine);
+    let _ = std::fs::remove_file(&path);
+}
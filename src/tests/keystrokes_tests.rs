@@ -0,0 +1,144 @@
+#![allow(non_snake_case)]
+
+use std::sync::Arc;
+
+use crate::{to_keystrokes, transliterate, EngineConfig, InputMethod, KeyAction, Keymap, ToneMark};
+
+const PARAGRAPH: &str = "Người Việt thường nói: \"Đường đi khó, không khó vì ngăn sông \
+cách núi.\"\nHOÀNG ĐẾ Quyết Hòa Thủy GIẶT ỦY (tool, wow, a1, bà., C:\\) 100%";
+
+fn viqr_subset_keymap() -> InputMethod {
+    InputMethod::Custom(Arc::new(
+        Keymap::builder("VIQR subset")
+            .key('\'', KeyAction::Tone(ToneMark::Acute))
+            .key('`', KeyAction::Tone(ToneMark::Grave))
+            .key('.', KeyAction::Tone(ToneMark::DotBelow))
+            .key('^', KeyAction::Circumflex)
+            .key('+', KeyAction::Horn)
+            .key('d', KeyAction::DStroke)
+            .literal_prefix(Some('\\'))
+            .build(),
+    ))
+}
+
+fn assert_round_trip(text: &str, input_method: InputMethod) {
+    let keys = to_keystrokes(text, input_method.clone())
+        .unwrap_or_else(|| panic!("no keystrokes for {text:?} with {input_method:?}"));
+    assert_eq!(
+        transliterate(&keys, input_method, EngineConfig::default()),
+        text,
+        "{keys:?}"
+    );
+}
+
+// ==================== Canonical Keystrokes ====================
+
+#[test]
+fn testTelexKeystrokes() {
+    assert_eq!(
+        to_keystrokes("người Việt", InputMethod::Telex).as_deref(),
+        Some("nguwowif Vieetj")
+    );
+    assert_eq!(
+        to_keystrokes("Đường ăn", InputMethod::Telex).as_deref(),
+        Some("Dduwowngf awn")
+    );
+}
+
+#[test]
+fn testVniKeystrokes() {
+    assert_eq!(
+        to_keystrokes("người Việt", InputMethod::Vni).as_deref(),
+        Some("ngu7o7i2 Vie6t5")
+    );
+    assert_eq!(
+        to_keystrokes("Đường ăn", InputMethod::Vni).as_deref(),
+        Some("D9u7o7ng2 a8n")
+    );
+}
+
+#[test]
+fn testViqrKeystrokes() {
+    assert_eq!(
+        to_keystrokes("người Việt", InputMethod::Viqr).as_deref(),
+        Some("ngu+o+i` Vie^t.")
+    );
+}
+
+#[test]
+fn testMicrosoftVietnameseKeystrokes() {
+    assert_eq!(
+        to_keystrokes("người Việt", InputMethod::MicrosoftVietnamese).as_deref(),
+        Some("ngươi\u{300} Viêt\u{323}")
+    );
+}
+
+#[test]
+fn testCustomKeymapKeystrokes() {
+    assert_eq!(
+        to_keystrokes("người Việt", viqr_subset_keymap()).as_deref(),
+        Some("ngu+o+i` Vie^t.")
+    );
+}
+
+#[test]
+fn testDecomposedInputIsNormalized() {
+    assert_eq!(
+        to_keystrokes("Vie\u{323}\u{302}t", InputMethod::Telex).as_deref(),
+        Some("Vieetj")
+    );
+}
+
+// ==================== Escapes ====================
+
+#[test]
+fn testTelexEscapesEnglishWords() {
+    assert_eq!(
+        to_keystrokes("tool", InputMethod::Telex).as_deref(),
+        Some("toool")
+    );
+    assert_round_trip("wow, class", InputMethod::Telex);
+}
+
+#[test]
+fn testVniEscapesDigitsAfterWords() {
+    assert_eq!(
+        to_keystrokes("a1", InputMethod::Vni).as_deref(),
+        Some("a11")
+    );
+}
+
+#[test]
+fn testViqrEscapesPunctuationAfterWords() {
+    assert_eq!(
+        to_keystrokes("bà.", InputMethod::Viqr).as_deref(),
+        Some("ba`\\.")
+    );
+}
+
+// ==================== Round Trips ====================
+
+#[test]
+fn testRoundTripEveryBuiltInMethod() {
+    for input_method in [
+        InputMethod::Telex,
+        InputMethod::SimpleTelex,
+        InputMethod::Vni,
+        InputMethod::Viqr,
+        InputMethod::MicrosoftVietnamese,
+    ] {
+        assert_round_trip(PARAGRAPH, input_method);
+    }
+}
+
+#[test]
+fn testRoundTripEmptyText() {
+    assert_eq!(to_keystrokes("", InputMethod::Telex).as_deref(), Some(""));
+}
+
+#[test]
+fn testUntypeableTextIsNone() {
+    // The keymap has no breve key, so ă cannot be typed.
+    assert_eq!(to_keystrokes("ăn", viqr_subset_keymap()), None);
+    assert_round_trip("ân", viqr_subset_keymap());
+}
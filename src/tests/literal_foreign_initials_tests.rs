@@ -0,0 +1,42 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::{apply_input, apply_input_with_config, apply_simple_telex_input};
+use crate::{EngineConfig, InputMethod};
+
+#[test]
+fn testLiteralForeignInitials() {
+    let config = EngineConfig::builder()
+        .literal_foreign_initials(true)
+        .build();
+    assert_eq!(apply_input_with_config("fix", config.clone()), "fix");
+    assert_eq!(apply_input_with_config("java", config.clone()), "java");
+    assert_eq!(apply_input_with_config("zoo", config.clone()), "zoo");
+    assert_eq!(apply_input_with_config("Fas", config.clone()), "Fas");
+    // Only the first key of a word counts; tone keys still work elsewhere.
+    assert_eq!(apply_input_with_config("af", config.clone()), "à");
+    assert_eq!(
+        apply_input_with_config("zoo vieejt", config.clone()),
+        "zoo việt"
+    );
+    // Standalone w is still ư in Telex.
+    assert_eq!(apply_input_with_config("wa", config), "ưa");
+}
+
+#[test]
+fn testLiteralForeignInitialsInSimpleTelex() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::SimpleTelex)
+        .literal_foreign_initials(true)
+        .build();
+    assert_eq!(apply_input_with_config("wifi", config.clone()), "wifi");
+    assert_eq!(apply_input_with_config("wow", config.clone()), "wow");
+    assert_eq!(apply_input_with_config("fix", config.clone()), "fix");
+    assert_eq!(apply_input_with_config("tuw", config), "tư");
+}
+
+#[test]
+fn testDefaultKeepsDualRoles() {
+    assert_eq!(apply_input("fix"), "fĩ");
+    assert_eq!(apply_input("zoo"), "zô");
+    assert_eq!(apply_simple_telex_input("fas"), "fá");
+}
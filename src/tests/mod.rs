@@ -1,8 +1,30 @@
+mod config_tests;
+mod convert_tests;
+mod english_tests;
+mod exclusion_tests;
+// These files wrap their tests in a module of the same name.
+#[allow(clippy::module_inception)]
 mod foreign_consonant_tests;
+#[allow(clippy::module_inception)]
 mod key_transformer_tests;
+mod keymap_tests;
+mod keystrokes_tests;
+mod literal_foreign_initials_tests;
+mod ms_vietnamese_tests;
+mod output_encoding_tests;
+mod quick_consonant_tests;
+mod quick_telex_tests;
+mod restore_tests;
+mod settings_tests;
+mod spelling_tests;
+mod strip_tests;
+mod syllable_tests;
+mod telex_bracket_tests;
 mod test_helpers;
 mod tone_cluster_tests;
 mod tone_placement_tests;
+mod transliterate_tests;
+mod viqr_tests;
 mod vni_legacy_tests_do_not_edit_or_update;
 mod vni_mapping_tests;
 mod w_transform_tests;
@@ -0,0 +1,119 @@
+#![allow(non_snake_case)]
+
+use std::ffi::{CStr, CString};
+
+use super::test_helpers::apply_input_with_config;
+use crate::ffi::{
+    vitype_engine_free, vitype_engine_free_string, vitype_engine_new, vitype_engine_process,
+    vitype_engine_set_input_method, vitype_engine_set_output_encoding,
+};
+use crate::{EngineConfig, InputMethod};
+
+const ACUTE: char = '\u{0301}';
+const GRAVE: char = '\u{0300}';
+const HOOK: char = '\u{0309}';
+const TILDE: char = '\u{0303}';
+const DOT: char = '\u{0323}';
+
+fn apply_ms_input(input: &str) -> String {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::MicrosoftVietnamese)
+        .build();
+    apply_input_with_config(input, config)
+}
+
+// MARK: - Direct Characters
+
+#[test]
+fn testDirectCharactersPassThrough() {
+    assert_eq!(apply_ms_input("ăâêôơưđ"), "ăâêôơưđ");
+    assert_eq!(apply_ms_input("ĂÂÊÔƠƯĐ"), "ĂÂÊÔƠƯĐ");
+    assert_eq!(apply_ms_input("aa dd ow"), "aa dd ow");
+}
+
+// MARK: - Tone Keys
+
+#[test]
+fn testToneKeysApplyToneMarks() {
+    assert_eq!(apply_ms_input(&format!("a{}", ACUTE)), "á");
+    assert_eq!(apply_ms_input(&format!("a{}", GRAVE)), "à");
+    assert_eq!(apply_ms_input(&format!("a{}", HOOK)), "ả");
+    assert_eq!(apply_ms_input(&format!("a{}", TILDE)), "ã");
+    assert_eq!(apply_ms_input(&format!("a{}", DOT)), "ạ");
+    assert_eq!(apply_ms_input(&format!("Vi{}êt Nam", DOT)), "Việt Nam");
+    assert_eq!(apply_ms_input(&format!("ngươi{}", GRAVE)), "người");
+    assert_eq!(apply_ms_input(&format!("đươ{}ng", GRAVE)), "đường");
+}
+
+#[test]
+fn testToneKeyWithoutVowelIsInsertedByHost() {
+    assert_eq!(
+        apply_ms_input(&format!("b{}", ACUTE)),
+        format!("b{}", ACUTE)
+    );
+    assert_eq!(
+        apply_ms_input(&format!("{}a", ACUTE)),
+        format!("{}a", ACUTE)
+    );
+}
+
+#[test]
+fn testToneKeyReplacesTone() {
+    assert_eq!(apply_ms_input(&format!("a{}{}", ACUTE, GRAVE)), "à");
+}
+
+#[test]
+fn testRepeatedToneKeyRemovesTone() {
+    assert_eq!(apply_ms_input(&format!("a{}{}", ACUTE, ACUTE)), "a");
+    assert_eq!(apply_ms_input(&format!("tô{}{}i", DOT, DOT)), "tôi");
+}
+
+// MARK: - Tone Placement
+
+#[test]
+fn testToneIsRepositionedAsVowelsAreTyped() {
+    assert_eq!(apply_ms_input(&format!("ho{}a", GRAVE)), "hòa");
+    assert_eq!(apply_ms_input(&format!("hoa{}i", GRAVE)), "hoài");
+    assert_eq!(apply_ms_input(&format!("thu{}ơ", ACUTE)), "thuớ");
+    assert_eq!(apply_ms_input(&format!("tiê{}ng", ACUTE)), "tiếng");
+}
+
+#[test]
+fn testAutoFixCanBeDisabled() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::MicrosoftVietnamese)
+        .auto_fix_tone(false)
+        .build();
+    assert_eq!(
+        apply_input_with_config(&format!("hoa{}i", GRAVE), config),
+        "hòai"
+    );
+}
+
+// MARK: - Output Encoding
+
+#[test]
+fn testFfiCompositeUnicodeOutput() {
+    let engine = vitype_engine_new();
+    vitype_engine_set_input_method(engine, 4);
+    vitype_engine_set_output_encoding(engine, 1);
+
+    for key in ["V", "i"] {
+        let key = CString::new(key).unwrap();
+        assert!(!vitype_engine_process(engine, key.as_ptr()).has_action);
+    }
+    let key = CString::new("ê").unwrap();
+    assert!(!vitype_engine_process(engine, key.as_ptr()).has_action);
+
+    let key = CString::new(DOT.to_string()).unwrap();
+    let result = vitype_engine_process(engine, key.as_ptr());
+    assert!(result.has_action);
+    assert_eq!(result.delete_count, 1);
+    let text = unsafe { CStr::from_ptr(result.text) }
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(text, "e\u{0323}\u{0302}");
+    vitype_engine_free_string(result.text);
+    vitype_engine_free(engine);
+}
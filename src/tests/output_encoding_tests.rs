@@ -0,0 +1,462 @@
+#![allow(non_snake_case)]
+
+use std::ffi::{CStr, CString};
+
+use super::test_helpers::apply_input;
+use crate::encoding::encode_output;
+use crate::ffi::{
+    vitype_engine_free, vitype_engine_free_string, vitype_engine_new, vitype_engine_process,
+    vitype_engine_set_delete_count_unit, vitype_engine_set_output_encoding,
+};
+use crate::{
+    encode_text, DeleteCountUnit, EngineConfig, KeyTransformAction, OutputEncoding, VitypeEngine,
+};
+
+/// Types `input` through the FFI and returns the text a host would have on screen, treating
+/// `delete_count` as a number of output characters.
+fn apply_ffi_input(input: &str, encoding: i32) -> String {
+    let engine = vitype_engine_new();
+    vitype_engine_set_output_encoding(engine, encoding);
+
+    let mut output: Vec<char> = Vec::new();
+    for ch in input.chars() {
+        let key = CString::new(ch.to_string()).unwrap();
+        let result = vitype_engine_process(engine, key.as_ptr());
+        if result.has_action {
+            for _ in 0..result.delete_count {
+                output.pop();
+            }
+            let text = unsafe { CStr::from_ptr(result.text) };
+            output.extend(text.to_str().unwrap().chars());
+            vitype_engine_free_string(result.text);
+        } else {
+            output.push(ch);
+        }
+    }
+    vitype_engine_free(engine);
+
+    output.into_iter().collect()
+}
+
+fn bytes_to_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| char::from(byte)).collect()
+}
+
+// MARK: - TCVN3
+
+#[test]
+fn testTcvn3Output() {
+    assert_eq!(
+        apply_ffi_input("Vieejt Nam", 2),
+        bytes_to_string(b"Vi\xD6t Nam")
+    );
+    assert_eq!(
+        apply_ffi_input("dduwowngf", 2),
+        bytes_to_string(b"\xAE\xAD\xEAng")
+    );
+    assert_eq!(
+        apply_ffi_input("aw aa ee oo ow uw", 2),
+        bytes_to_string(b"\xA8 \xA9 \xAA \xAB \xAC \xAD")
+    );
+}
+
+#[test]
+fn testTcvn3UppercaseToneUsesLowercaseCode() {
+    assert_eq!(apply_ffi_input("VIEEJT", 2), bytes_to_string(b"VI\xD6T"));
+    assert_eq!(
+        apply_ffi_input("AW AA EE OO OW UW DD", 2),
+        bytes_to_string(b"\xA1 \xA2 \xA3 \xA4 \xA5 \xA6 \xA7")
+    );
+}
+
+// MARK: - VISCII
+
+#[test]
+fn testVisciiOutput() {
+    assert_eq!(
+        apply_ffi_input("Vieejt Nam", 3),
+        bytes_to_string(b"Vi\xAEt Nam")
+    );
+    assert_eq!(apply_ffi_input("VIEEJT", 3), bytes_to_string(b"VI\x8ET"));
+    assert_eq!(
+        apply_ffi_input("dduwowngf", 3),
+        bytes_to_string(b"\xF0\xDF\xB6ng")
+    );
+    assert_eq!(
+        apply_ffi_input("YJ YR YX", 3),
+        bytes_to_string(b"\x1E \x14 \x19")
+    );
+}
+
+// MARK: - VNI-Windows
+
+#[test]
+fn testVniWindowsOutput() {
+    assert_eq!(
+        apply_ffi_input("Vieejt Nam", 4),
+        bytes_to_string(b"Vie\xE4t Nam")
+    );
+    assert_eq!(
+        apply_ffi_input("nguwowif", 4),
+        bytes_to_string(b"ng\xF6\xF4\xF8i")
+    );
+    assert_eq!(
+        apply_ffi_input("DDUWOWNGF", 4),
+        bytes_to_string(b"\xD1\xD6\xD4\xD8NG")
+    );
+    assert_eq!(
+        apply_ffi_input("aws ij yj", 4),
+        bytes_to_string(b"a\xE9 \xF2 \xEE")
+    );
+}
+
+#[test]
+fn testVniWindowsDeleteCountCountsDiacriticBytes() {
+    for input in [
+        "hoafi",
+        "nguwowif",
+        "DDUWOWNGF",
+        "tieengs Vieejt",
+        "aaa",
+        "ddd",
+        "asf",
+        "ass",
+        "Quyeenf",
+        "giaf",
+        "khoer",
+    ] {
+        assert_eq!(
+            apply_ffi_input(input, 4),
+            encode_output(apply_input(input), OutputEncoding::VniWindows),
+            "{}",
+            input
+        );
+    }
+}
+
+// MARK: - Windows-1258
+
+#[test]
+fn testCp1258KeepsShapedLettersAndDecomposesTones() {
+    assert_eq!(
+        apply_ffi_input("Vieejt Nam", 8),
+        bytes_to_string(b"Vi\xEA\xF2t Nam")
+    );
+    assert_eq!(apply_ffi_input("tas", 8), bytes_to_string(b"ta\xEC"));
+    assert_eq!(
+        apply_ffi_input("DDUWOWNGF", 8),
+        bytes_to_string(b"\xD0\xDD\xD5\xCCNG")
+    );
+    assert_eq!(apply_ffi_input("aw aa", 8), bytes_to_string(b"\xE3 \xE2"));
+}
+
+#[test]
+fn testCp1258DeleteCountCountsToneBytes() {
+    for input in [
+        "hoafi",
+        "nguwowif",
+        "tieengs Vieejt",
+        "asf",
+        "ass",
+        "aas",
+        "ddd",
+        "khoer",
+    ] {
+        assert_eq!(
+            apply_ffi_input(input, 8),
+            encode_output(apply_input(input), OutputEncoding::Cp1258),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn testCodePagesWriteQuestionMarkForUnmappedCharacters() {
+    for encoding in [
+        OutputEncoding::Tcvn3,
+        OutputEncoding::Viscii,
+        OutputEncoding::VniWindows,
+        OutputEncoding::Cp1258,
+    ] {
+        let output = encode_output("việt ü©".to_string(), encoding);
+        assert!(output.ends_with(" ??"), "{encoding:?}");
+        // Engine output and whole-text conversion agree byte for byte.
+        let bytes: Vec<u8> = output.chars().map(|ch| ch as u8).collect();
+        assert_eq!(bytes, encode_text("việt ü©", encoding), "{encoding:?}");
+    }
+}
+
+// MARK: - ASCII Encodings
+
+#[test]
+fn testViqrOutput() {
+    assert_eq!(apply_ffi_input("Vieejt Nam", 5), "Vie^.t Nam");
+    assert_eq!(apply_ffi_input("nguwowif", 5), "ngu+o+`i");
+    assert_eq!(apply_ffi_input("DDAWNGS", 5), "DDA('NG");
+    assert_eq!(apply_ffi_input("hoir ngax", 5), "ho?i nga~");
+}
+
+#[test]
+fn testHtmlEntityOutput() {
+    assert_eq!(apply_ffi_input("Vieejt Nam", 6), "Vi&#7879;t Nam");
+    assert_eq!(apply_ffi_input("ddi", 6), "&#273;i");
+}
+
+#[test]
+fn testUnicodeEscapeOutput() {
+    assert_eq!(apply_ffi_input("Vieejt Nam", 7), "Vi\\u1EC7t Nam");
+    assert_eq!(apply_ffi_input("DDi", 7), "\\u0110i");
+}
+
+#[test]
+fn testAsciiEncodingsDeleteCountCountsEmittedCharacters() {
+    for input in [
+        "hoafi",
+        "nguwowif",
+        "tieengs Vieejt",
+        "aaa",
+        "ddd",
+        "asf",
+        "ass",
+        "khoer",
+    ] {
+        let unicode = apply_input(input);
+        for (code, encoding) in [
+            (5, OutputEncoding::Viqr),
+            (6, OutputEncoding::HtmlEntity),
+            (7, OutputEncoding::UnicodeEscape),
+        ] {
+            assert_eq!(
+                apply_ffi_input(input, code),
+                encode_output(unicode.clone(), encoding),
+                "{}",
+                input
+            );
+        }
+    }
+}
+
+// MARK: - Delete Count
+
+#[test]
+fn testDeleteCountMatchesSingleByteOutput() {
+    for input in [
+        "hoafi",
+        "nguwowif",
+        "toois",
+        "tieengs Vieejt",
+        "dd",
+        "uow",
+        "asa",
+    ] {
+        let unicode = apply_input(input);
+        for (code, encoding) in [(2, OutputEncoding::Tcvn3), (3, OutputEncoding::Viscii)] {
+            assert_eq!(
+                apply_ffi_input(input, code),
+                encode_output(unicode.clone(), encoding),
+                "{}",
+                input
+            );
+        }
+    }
+}
+
+#[test]
+fn testCompositeUnicodeDeleteCountCountsCodePoints() {
+    for input in [
+        "asf",
+        "ass",
+        "hoafi",
+        "nguwowif",
+        "tieengs Vieejt",
+        "aaa",
+        "Vieejt Nam",
+    ] {
+        assert_eq!(
+            apply_ffi_input(input, 1),
+            encode_output(apply_input(input), OutputEncoding::CompositeUnicode),
+            "{}",
+            input
+        );
+    }
+}
+
+/// Applies `action` to `screen` with `delete_count` in output characters.
+fn apply_action(screen: &mut Vec<char>, key: char, action: Option<KeyTransformAction>) {
+    match action {
+        Some(action) => {
+            for _ in 0..action.delete_count {
+                screen.pop();
+            }
+            screen.extend(action.text.chars());
+        }
+        None => screen.push(key),
+    }
+}
+
+#[test]
+fn testDeleteCountFollowsEncodingUsedWhenEmitted() {
+    let mut engine = VitypeEngine::new();
+    let mut screen = Vec::new();
+    for key in "as".chars() {
+        let action = engine.process_encoded(&key.to_string());
+        apply_action(&mut screen, key, action);
+    }
+    assert_eq!(screen, vec!['á']);
+
+    // "á" went out as one character; replacing it deletes one character.
+    engine.set_output_encoding(OutputEncoding::CompositeUnicode);
+    let action = engine.process_encoded("f").unwrap();
+    assert_eq!(action.delete_count, 1);
+    apply_action(&mut screen, 'f', Some(action));
+
+    // "à" went out decomposed; replacing it deletes both code points.
+    engine.set_output_encoding(OutputEncoding::Unicode);
+    let action = engine.process_encoded("r").unwrap();
+    assert_eq!(action.delete_count, 2);
+    apply_action(&mut screen, 'r', Some(action));
+    assert_eq!(screen.into_iter().collect::<String>(), "ả");
+}
+
+#[test]
+fn testDeleteCountAfterBackspace() {
+    let mut engine = VitypeEngine::new();
+    engine.set_output_encoding(OutputEncoding::VniWindows);
+    let mut screen = Vec::new();
+    for key in "tieens".chars() {
+        let action = engine.process_encoded(&key.to_string());
+        apply_action(&mut screen, key, action);
+    }
+
+    // Backspace over "n", then retype the ending and change the tone across it.
+    screen.pop();
+    engine.delete_last_character();
+    for key in "ngf".chars() {
+        let action = engine.process_encoded(&key.to_string());
+        apply_action(&mut screen, key, action);
+    }
+    assert_eq!(
+        screen.into_iter().collect::<String>(),
+        encode_output("tiềng".to_string(), OutputEncoding::VniWindows)
+    );
+}
+
+// MARK: - Delete Count Unit
+
+/// Types `keys` and returns the `delete_count` of the last key.
+fn last_delete_count(keys: &str, encoding: OutputEncoding, unit: DeleteCountUnit) -> usize {
+    let config = EngineConfig::builder()
+        .output_encoding(encoding)
+        .delete_count_unit(unit)
+        .build();
+    let mut engine = VitypeEngine::with_config(config);
+    let mut delete_count = 0;
+    for key in keys.chars() {
+        delete_count = engine
+            .process_encoded(&key.to_string())
+            .map_or(0, |action| action.delete_count);
+    }
+    delete_count
+}
+
+#[test]
+fn testDeleteCountUnits() {
+    use DeleteCountUnit::*;
+    use OutputEncoding::*;
+
+    // Replacing "ê" with "ệ".
+    assert_eq!(last_delete_count("veej", Unicode, CodePoints), 1);
+    assert_eq!(last_delete_count("veej", Unicode, Utf16CodeUnits), 1);
+    assert_eq!(last_delete_count("veej", Unicode, Utf8Bytes), 2);
+    assert_eq!(last_delete_count("veej", Unicode, GraphemeClusters), 1);
+    assert_eq!(last_delete_count("veej", CompositeUnicode, CodePoints), 2);
+    assert_eq!(
+        last_delete_count("veej", CompositeUnicode, Utf16CodeUnits),
+        2
+    );
+    assert_eq!(last_delete_count("veej", CompositeUnicode, Utf8Bytes), 3);
+    assert_eq!(
+        last_delete_count("veej", CompositeUnicode, GraphemeClusters),
+        1
+    );
+    assert_eq!(last_delete_count("veej", HtmlEntity, Utf8Bytes), 6);
+
+    // Auto fix tone rewrites "hòa" + "i" as "hoài": deletes "òa".
+    assert_eq!(last_delete_count("hoafi", Unicode, Utf8Bytes), 3);
+    assert_eq!(
+        last_delete_count("hoafi", CompositeUnicode, GraphemeClusters),
+        2
+    );
+}
+
+#[test]
+fn testDeleteCountUnitsInCodePages() {
+    use DeleteCountUnit::*;
+    use OutputEncoding::*;
+
+    // Code pages take one byte per character: VNI-Windows "ê" is "e" + "â".
+    assert_eq!(last_delete_count("veej", VniWindows, Utf8Bytes), 2);
+    assert_eq!(last_delete_count("veej", Tcvn3, Utf8Bytes), 1);
+    assert_eq!(last_delete_count("veej", Cp1258, Utf8Bytes), 1);
+
+    // The diacritic byte is a character of its own.
+    assert_eq!(last_delete_count("veej", VniWindows, GraphemeClusters), 2);
+}
+
+#[test]
+fn testFfiSetDeleteCountUnit() {
+    let engine = vitype_engine_new();
+    vitype_engine_set_delete_count_unit(engine, 2);
+    assert_eq!(
+        unsafe { (*engine).delete_count_unit() },
+        DeleteCountUnit::Utf8Bytes
+    );
+
+    let mut last = None;
+    for key in ["a", "s", "f"] {
+        let key = CString::new(key).unwrap();
+        let result = vitype_engine_process(engine, key.as_ptr());
+        if result.has_action {
+            last = Some(result.delete_count);
+            vitype_engine_free_string(result.text);
+        }
+    }
+    assert_eq!(last, Some(2));
+    vitype_engine_free(engine);
+}
+
+// MARK: - Settings
+
+#[test]
+fn testDeleteCountUnitSettingsNames() {
+    for (name, unit) in [
+        ("code_points", DeleteCountUnit::CodePoints),
+        ("utf16_code_units", DeleteCountUnit::Utf16CodeUnits),
+        ("utf8_bytes", DeleteCountUnit::Utf8Bytes),
+        ("grapheme_clusters", DeleteCountUnit::GraphemeClusters),
+    ] {
+        let config = EngineConfig::builder().delete_count_unit(unit).build();
+        let text = config.to_toml_string();
+        assert!(text.contains(&format!("delete_count_unit = \"{}\"", name)));
+        assert_eq!(EngineConfig::from_toml_str(&text).unwrap(), config);
+    }
+}
+
+#[test]
+fn testSettingsNames() {
+    for (name, encoding) in [
+        ("tcvn3", OutputEncoding::Tcvn3),
+        ("viscii", OutputEncoding::Viscii),
+        ("vni_windows", OutputEncoding::VniWindows),
+        ("viqr", OutputEncoding::Viqr),
+        ("html_entity", OutputEncoding::HtmlEntity),
+        ("unicode_escape", OutputEncoding::UnicodeEscape),
+        ("cp1258", OutputEncoding::Cp1258),
+    ] {
+        let config = EngineConfig::builder().output_encoding(encoding).build();
+        let text = config.to_toml_string();
+        assert!(text.contains(&format!("output_encoding = \"{}\"", name)));
+        assert_eq!(EngineConfig::from_toml_str(&text).unwrap(), config);
+    }
+}
@@ -0,0 +1,115 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::{action, apply_input, apply_input_with_config};
+use crate::{EngineConfig, VitypeEngine};
+
+fn apply_quick_input(input: &str) -> String {
+    apply_input_with_config(
+        input,
+        EngineConfig::builder().quick_consonants(true).build(),
+    )
+}
+
+// MARK: - Start Consonants
+
+#[test]
+fn testQuickStartConsonants() {
+    assert_eq!(apply_quick_input("fa"), "pha");
+    assert_eq!(apply_quick_input("jaf"), "già");
+    assert_eq!(apply_quick_input("was"), "quá");
+    assert_eq!(apply_quick_input("Fos"), "Phó");
+    assert_eq!(apply_quick_input("wyeen"), "quyên");
+}
+
+#[test]
+fn testQuickStartAllCaps() {
+    assert_eq!(apply_quick_input("FA"), "PHA");
+    assert_eq!(apply_quick_input("JAF"), "GIÀ");
+    assert_eq!(apply_quick_input("WAS"), "QUÁ");
+    assert_eq!(apply_quick_input("FOS BA"), "PHÓ BA");
+    assert_eq!(apply_quick_input("Fa"), "Pha");
+    assert_eq!(apply_quick_input("FFIX"), "FIX");
+    assert_eq!(apply_quick_input("JF"), "GÌ");
+
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().quick_consonants(true).build());
+    assert_eq!(engine.process("F"), Some(action(0, "Ph")));
+    assert_eq!(engine.process("A"), Some(action(1, "HA")));
+    assert_eq!(engine.process("G"), Some(action(0, "NG")));
+}
+
+#[test]
+fn testQuickStartOnlyAtWordStart() {
+    assert_eq!(apply_quick_input("af"), "à");
+    assert_eq!(apply_quick_input("aw"), "ă");
+    assert_eq!(apply_quick_input("ba jaf"), "ba già");
+}
+
+#[test]
+fn testQuickStartEscape() {
+    assert_eq!(apply_quick_input("ff"), "f");
+    assert_eq!(apply_quick_input("ffix"), "fix");
+    assert_eq!(apply_quick_input("wwifi"), "wifi");
+
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().quick_consonants(true).build());
+    assert_eq!(engine.process("j"), Some(action(0, "gi")));
+    assert_eq!(engine.process("j"), Some(action(2, "j")));
+}
+
+#[test]
+fn testQuickStartWinsOverLiteralForeignInitials() {
+    let config = EngineConfig::builder()
+        .quick_consonants(true)
+        .literal_foreign_initials(true)
+        .build();
+    assert_eq!(apply_input_with_config("fa", config.clone()), "pha");
+    assert_eq!(apply_input_with_config("zoo", config), "zoo");
+}
+
+// MARK: - End Consonants
+
+#[test]
+fn testQuickEndConsonants() {
+    assert_eq!(apply_quick_input("ag"), "ang");
+    assert_eq!(apply_quick_input("ah"), "anh");
+    assert_eq!(apply_quick_input("ak"), "ach");
+    assert_eq!(apply_quick_input("tieegs"), "tiếng");
+    assert_eq!(apply_quick_input("thuyk"), "thuych");
+    assert_eq!(apply_quick_input("AG"), "ANG");
+    assert_eq!(apply_quick_input("Ag"), "Ang");
+}
+
+#[test]
+fn testQuickEndRequiresValidSyllable() {
+    assert_eq!(apply_quick_input("ig"), "ig");
+    assert_eq!(apply_quick_input("oh"), "oh");
+    assert_eq!(apply_quick_input("aig"), "aig");
+    assert_eq!(apply_quick_input("ek"), "ek");
+    assert_eq!(apply_quick_input("ang"), "ang");
+}
+
+#[test]
+fn testQuickEndMovesTone() {
+    assert_eq!(apply_quick_input("hoafg"), "hoàng");
+
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().quick_consonants(true).build());
+    for key in ["h", "o", "a", "f"] {
+        engine.process(key);
+    }
+    assert_eq!(engine.process("g"), Some(action(2, "oàng")));
+}
+
+#[test]
+fn testQuickEndEscape() {
+    assert_eq!(apply_quick_input("agg"), "ag");
+    assert_eq!(apply_quick_input("AGG"), "AG");
+    assert_eq!(apply_quick_input("akk"), "ak");
+}
+
+#[test]
+fn testQuickConsonantsDisabledByDefault() {
+    assert_eq!(apply_input("fa"), "fa");
+    assert_eq!(apply_input("ag"), "ag");
+}
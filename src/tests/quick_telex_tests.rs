@@ -0,0 +1,99 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::{action, apply_input, apply_input_with_config};
+use crate::{EngineConfig, VitypeEngine};
+
+fn apply_quick_telex_input(input: &str) -> String {
+    apply_input_with_config(input, EngineConfig::builder().quick_telex(true).build())
+}
+
+// MARK: - Doubled Initial Consonants
+
+#[test]
+fn testDoubledInitialsExpand() {
+    assert_eq!(apply_quick_telex_input("cc"), "ch");
+    assert_eq!(apply_quick_telex_input("gg"), "gi");
+    assert_eq!(apply_quick_telex_input("kk"), "kh");
+    assert_eq!(apply_quick_telex_input("nn"), "ng");
+    assert_eq!(apply_quick_telex_input("pp"), "ph");
+    assert_eq!(apply_quick_telex_input("qq"), "qu");
+    assert_eq!(apply_quick_telex_input("tt"), "th");
+}
+
+#[test]
+fn testDoubledInitialAction() {
+    let mut engine = VitypeEngine::with_config(EngineConfig::builder().quick_telex(true).build());
+    assert_eq!(engine.process("n"), None);
+    assert_eq!(engine.process("n"), Some(action(0, "g")));
+}
+
+#[test]
+fn testDoubledInitialsKeepCase() {
+    assert_eq!(apply_quick_telex_input("Cc"), "Ch");
+    assert_eq!(apply_quick_telex_input("NN"), "NG");
+    assert_eq!(apply_quick_telex_input("TToong"), "THông");
+}
+
+#[test]
+fn testWordsWithQuickInitials() {
+    assert_eq!(apply_quick_telex_input("nnuowif"), "người");
+    assert_eq!(apply_quick_telex_input("ttaaps"), "thấp");
+    assert_eq!(apply_quick_telex_input("ggaf"), "già");
+    assert_eq!(apply_quick_telex_input("qqas"), "quá");
+    assert_eq!(apply_quick_telex_input("kkoong"), "không");
+    assert_eq!(apply_quick_telex_input("ppos"), "phó");
+    assert_eq!(apply_quick_telex_input("ccaof"), "chào");
+}
+
+#[test]
+fn testOnlyWordInitialPairsExpand() {
+    assert_eq!(apply_quick_telex_input("ann"), "ann");
+    assert_eq!(apply_quick_telex_input("ett"), "ett");
+    assert_eq!(apply_quick_telex_input("ct"), "ct");
+    assert_eq!(apply_quick_telex_input("ddi"), "đi");
+}
+
+// MARK: - Escape
+
+#[test]
+fn testThirdKeyRestoresLiteralPair() {
+    assert_eq!(apply_quick_telex_input("ccc"), "cc");
+    assert_eq!(apply_quick_telex_input("NNN"), "NN");
+    assert_eq!(apply_quick_telex_input("gggo"), "ggo");
+
+    let mut engine = VitypeEngine::with_config(EngineConfig::builder().quick_telex(true).build());
+    engine.process("t");
+    engine.process("t");
+    assert_eq!(engine.process("t"), Some(action(1, "t")));
+}
+
+#[test]
+fn testEscapeLocksTransformsUntilBoundary() {
+    assert_eq!(apply_quick_telex_input("tttas"), "ttas");
+    assert_eq!(apply_quick_telex_input("ttt tas"), "tt tá");
+}
+
+// MARK: - Raw Buffer
+
+#[test]
+fn testBackspaceRebuildsDigraph() {
+    let mut engine = VitypeEngine::with_config(EngineConfig::builder().quick_telex(true).build());
+    for key in ["t", "t", "a"] {
+        engine.process(key);
+    }
+    engine.delete_last_character();
+    assert_eq!(engine.process("o"), None);
+    assert_eq!(engine.process("s"), Some(action(1, "ó")));
+}
+
+#[test]
+fn testForeignWordRestoresRawKeys() {
+    assert_eq!(apply_quick_telex_input("ttoday"), "ttoday");
+    assert_eq!(apply_quick_telex_input("ppeople"), "ppeople");
+}
+
+#[test]
+fn testDisabledByDefault() {
+    assert_eq!(apply_input("cc"), "cc");
+    assert_eq!(apply_input("nnuowif"), "nnười");
+}
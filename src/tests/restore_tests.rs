@@ -0,0 +1,104 @@
+#![allow(non_snake_case)]
+
+use std::ffi::{CStr, CString};
+
+use super::test_helpers::{action, apply_input, apply_input_with_config};
+use crate::ffi::{
+    vitype_engine_free, vitype_engine_free_string, vitype_engine_new, vitype_engine_process,
+    vitype_engine_set_restore_invalid_words,
+};
+use crate::{EngineConfig, InputMethod, VitypeEngine};
+
+fn restoring(input: &str) -> String {
+    apply_input_with_config(
+        input,
+        EngineConfig::builder().restore_invalid_words(true).build(),
+    )
+}
+
+#[test]
+fn testInvalidWordsAreRestoredAtBoundary() {
+    assert_eq!(restoring("text "), "text ");
+    assert_eq!(restoring("fix, usb."), "fix, usb.");
+    assert_eq!(restoring("next\n"), "next\n");
+}
+
+#[test]
+fn testValidWordsAreKept() {
+    assert_eq!(restoring("tieengs vieetj "), "tiếng việt ");
+    // "mĩ" is a valid syllable, so there is no telling it apart from an English "mix".
+    assert_eq!(restoring("mix "), "mĩ ");
+}
+
+#[test]
+fn testWordsWithoutVowelsAreKept() {
+    assert_eq!(restoring("100dd "), "100đ ");
+}
+
+#[test]
+fn testUntransformedWordsNeedNoAction() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().restore_invalid_words(true).build());
+    for key in "window".chars() {
+        engine.process(&key.to_string());
+    }
+    assert_eq!(engine.process(" "), None);
+}
+
+#[test]
+fn testRestoreActionReplacesWordAndTypesBoundary() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().restore_invalid_words(true).build());
+    for key in "text".chars() {
+        engine.process(&key.to_string());
+    }
+    assert_eq!(engine.process(" "), Some(action(3, "text ")));
+}
+
+#[test]
+fn testRestoreIsOffByDefault() {
+    assert_eq!(apply_input("text "), "tẽt ");
+}
+
+#[test]
+fn testRestoredWordStaysLiteralAfterBackspace() {
+    let mut engine =
+        VitypeEngine::with_config(EngineConfig::builder().restore_invalid_words(true).build());
+    for key in "text ".chars() {
+        engine.process(&key.to_string());
+    }
+    engine.delete_last_character();
+    // The word is edited as typed, so tone keys no longer apply.
+    assert_eq!(engine.process("s"), None);
+}
+
+#[test]
+fn testRestoreInVni() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .restore_invalid_words(true)
+        .build();
+    assert_eq!(apply_input_with_config("cap2 cap1 ", config), "cap2 cáp ");
+}
+
+#[test]
+fn testFfiRestoreInvalidWords() {
+    let engine = vitype_engine_new();
+    vitype_engine_set_restore_invalid_words(engine, true);
+    for key in "text".chars() {
+        let key = CString::new(key.to_string()).unwrap();
+        let result = vitype_engine_process(engine, key.as_ptr());
+        vitype_engine_free_string(result.text);
+    }
+
+    let space = CString::new(" ").unwrap();
+    let result = vitype_engine_process(engine, space.as_ptr());
+    assert!(result.has_action);
+    assert_eq!(result.delete_count, 3);
+    assert_eq!(
+        unsafe { CStr::from_ptr(result.text) }.to_str().unwrap(),
+        "text "
+    );
+    vitype_engine_free_string(result.text);
+    vitype_engine_free(engine);
+}
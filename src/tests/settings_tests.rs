@@ -0,0 +1,118 @@
+#![allow(non_snake_case)]
+
+use std::ffi::CString;
+
+use super::test_helpers::temp_path;
+use crate::ffi::{vitype_engine_free, vitype_engine_load_settings, vitype_engine_new};
+use crate::{
+    EngineConfig, InputMethod, OutputEncoding, SettingsError, TonePlacement, VitypeEngine,
+    SETTINGS_VERSION,
+};
+
+#[test]
+fn testRoundTrip() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .output_encoding(OutputEncoding::CompositeUnicode)
+        .tone_placement(TonePlacement::NucleusOnly)
+        .auto_fix_tone(false)
+        .free_tone_placement(true)
+        .build();
+    let text = config.to_toml_string();
+    assert!(text.contains("version = 1"));
+    assert!(text.contains("input_method = \"vni\""));
+    assert_eq!(EngineConfig::from_toml_str(&text).unwrap(), config);
+
+    let text = config.to_json_string();
+    assert_eq!(EngineConfig::from_json_str(&text).unwrap(), config);
+}
+
+#[test]
+fn testMissingKeysUseDefaults() {
+    let config = EngineConfig::from_toml_str("version = 1\ninput_method = \"vni\"\n").unwrap();
+    assert_eq!(
+        config,
+        EngineConfig::builder()
+            .input_method(InputMethod::Vni)
+            .build()
+    );
+}
+
+#[test]
+fn testUnknownKeysAreIgnored() {
+    let text =
+        "version = 1\nauto_fix_tone = false\nfuture_option = \"on\"\n\n[future_table]\nx = 1\n";
+    let config = EngineConfig::from_toml_str(text).unwrap();
+    assert!(!config.auto_fix_tone);
+
+    let json = r#"{"version": 1, "tone_placement": "nucleus_only", "future": [1, 2]}"#;
+    let config = EngineConfig::from_json_str(json).unwrap();
+    assert_eq!(config.tone_placement, TonePlacement::NucleusOnly);
+}
+
+#[test]
+fn testValidationErrors() {
+    assert!(matches!(
+        EngineConfig::from_toml_str("input_method = \"vni\""),
+        Err(SettingsError::MissingVersion)
+    ));
+    assert!(matches!(
+        EngineConfig::from_toml_str(&format!("version = {}", SETTINGS_VERSION + 1)),
+        Err(SettingsError::UnsupportedVersion(2))
+    ));
+    match EngineConfig::from_toml_str("version = 1\ninput_method = \"dvorak\"") {
+        Err(SettingsError::InvalidValue { key, value }) => {
+            assert_eq!(key, "input_method");
+            assert_eq!(value, "dvorak");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(matches!(
+        EngineConfig::from_toml_str("version = 1\nauto_fix_tone = \"yes\""),
+        Err(SettingsError::Parse(_))
+    ));
+    assert!(matches!(
+        EngineConfig::from_json_str("{"),
+        Err(SettingsError::Parse(_))
+    ));
+}
+
+#[test]
+fn testSaveAndLoadByExtension() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .free_tone_placement(true)
+        .build();
+    for name in ["settings.toml", "settings.json"] {
+        let path = temp_path(name);
+        config.save(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.trim_start().starts_with('{'), name.ends_with(".json"));
+        assert_eq!(EngineConfig::load(&path).unwrap(), config);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    assert!(matches!(
+        EngineConfig::load(temp_path("missing.toml")),
+        Err(SettingsError::Io(_))
+    ));
+}
+
+#[test]
+fn testFfiLoadSettings() {
+    let path = temp_path("ffi.toml");
+    std::fs::write(&path, "version = 1\ninput_method = \"vni\"\n").unwrap();
+    let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+    let engine = vitype_engine_new();
+    assert_eq!(vitype_engine_load_settings(engine, c_path.as_ptr()), 0);
+    assert_eq!(unsafe { (*engine).input_method() }, InputMethod::Vni);
+
+    std::fs::write(&path, "version = 1\ntone_placement = \"middle\"\n").unwrap();
+    assert_eq!(vitype_engine_load_settings(engine, c_path.as_ptr()), 4);
+    vitype_engine_free(engine);
+    let _ = std::fs::remove_file(&path);
+
+    let engine: *mut VitypeEngine = std::ptr::null_mut();
+    assert_eq!(vitype_engine_load_settings(engine, c_path.as_ptr()), -1);
+}
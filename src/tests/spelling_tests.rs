@@ -0,0 +1,186 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::{apply_input, apply_input_with_config};
+use crate::{check_spelling, EngineConfig, InputMethod, SpellingError, Syllable, ToneMark};
+
+fn spell_checked(input: &str) -> String {
+    let config = EngineConfig::builder().check_spelling(true).build();
+    apply_input_with_config(input, config)
+}
+
+// ==================== Validator ====================
+
+#[test]
+fn testValidSyllables() {
+    for word in [
+        "a",
+        "ăn",
+        "ấy",
+        "nghiêng",
+        "người",
+        "quyết",
+        "giường",
+        "gì",
+        "khuya",
+        "hoạch",
+        "thuở",
+        "kỹ",
+        "ghế",
+        "nghe",
+        "uých",
+        "oanh",
+        "Việt",
+        "ĐƯỜNG",
+    ] {
+        assert!(
+            check_spelling(word).is_ok(),
+            "{word:?}: {:?}",
+            check_spelling(word)
+        );
+    }
+}
+
+#[test]
+fn testInvalidOnset() {
+    assert_eq!(
+        check_spelling("fla"),
+        Err(SpellingError::InvalidOnset("fl".to_string()))
+    );
+    assert_eq!(
+        check_spelling("wa"),
+        Err(SpellingError::InvalidOnset("w".to_string()))
+    );
+}
+
+#[test]
+fn testInvalidCoda() {
+    assert_eq!(
+        check_spelling("bank"),
+        Err(SpellingError::InvalidCoda("nk".to_string()))
+    );
+}
+
+#[test]
+fn testOnsetSpellingBeforeFrontVowels() {
+    let expect = |word: &str, onset: &str, expected: &str| {
+        assert_eq!(
+            check_spelling(word),
+            Err(SpellingError::OnsetSpelling {
+                onset: onset.to_string(),
+                expected: expected.to_string(),
+            }),
+            "{word:?}"
+        );
+    };
+    expect("ngi", "ng", "ngh");
+    expect("nghà", "ngh", "ng");
+    expect("ce", "c", "k");
+    expect("ka", "k", "c");
+    expect("gế", "g", "gh");
+    expect("gha", "gh", "g");
+    expect("Ngi", "Ng", "ngh");
+}
+
+#[test]
+fn testInvalidRhymes() {
+    for (word, rhyme) in [
+        ("tiê", "iê"),
+        ("mian", "ian"),
+        ("tien", "ien"),
+        ("ăch", "ăch"),
+        ("ă", "ă"),
+        ("onh", "onh"),
+    ] {
+        assert_eq!(
+            check_spelling(word),
+            Err(SpellingError::InvalidRhyme(rhyme.to_string())),
+            "{word:?}"
+        );
+    }
+}
+
+#[test]
+fn testValidateRejectsEmptyNucleus() {
+    let syllable = Syllable {
+        onset: "b".to_string(),
+        glide: None,
+        nucleus: String::new(),
+        coda: "ng".to_string(),
+        tone: None,
+    };
+    assert_eq!(syllable.validate(), Err(SpellingError::NotASyllable));
+}
+
+#[test]
+fn testStopFinalsOnlyTakeAcuteOrDotBelow() {
+    assert!(check_spelling("cáp").is_ok());
+    assert!(check_spelling("cạch").is_ok());
+    assert_eq!(
+        check_spelling("càp"),
+        Err(SpellingError::ToneNotAllowed {
+            coda: "p".to_string(),
+            tone: Some(ToneMark::Grave),
+        })
+    );
+    assert_eq!(
+        check_spelling("bươc"),
+        Err(SpellingError::ToneNotAllowed {
+            coda: "c".to_string(),
+            tone: None,
+        })
+    );
+}
+
+#[test]
+fn testNotASyllable() {
+    for word in ["", "hello", "nh", "a-b"] {
+        assert_eq!(
+            check_spelling(word),
+            Err(SpellingError::NotASyllable),
+            "{word:?}"
+        );
+    }
+}
+
+#[test]
+fn testSpellingErrorDisplay() {
+    assert_eq!(
+        check_spelling("ngi").unwrap_err().to_string(),
+        "`ng` is spelled `ngh` before this vowel"
+    );
+}
+
+// ==================== Engine ====================
+
+#[test]
+fn testCheckSpellingRefusesToneOnStopFinal() {
+    assert_eq!(spell_checked("capf"), "capf");
+    assert_eq!(spell_checked("capr"), "capr");
+    assert_eq!(spell_checked("caps"), "cáp");
+    assert_eq!(spell_checked("buwowcj"), "bược");
+    assert_eq!(apply_input("capf"), "càp");
+}
+
+#[test]
+fn testCheckSpellingRefusesToneOnMisspelledOnset() {
+    assert_eq!(spell_checked("ngis"), "ngis");
+    assert_eq!(spell_checked("nghis"), "nghí");
+    assert_eq!(apply_input("ngis"), "ngí");
+}
+
+#[test]
+fn testCheckSpellingAllowsToneBeforeCoda() {
+    assert_eq!(spell_checked("tieesng"), "tiếng");
+    assert_eq!(spell_checked("vieejt"), "việt");
+    assert_eq!(spell_checked("ddaau"), "đâu");
+}
+
+#[test]
+fn testCheckSpellingInVni() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .check_spelling(true)
+        .build();
+    assert_eq!(apply_input_with_config("cap2", config.clone()), "cap2");
+    assert_eq!(apply_input_with_config("cap1", config), "cáp");
+}
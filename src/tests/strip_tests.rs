@@ -0,0 +1,89 @@
+#![allow(non_snake_case)]
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use crate::ffi::{
+    vitype_engine_free_string, vitype_remove_diacritics, vitype_remove_tones, vitype_slugify,
+};
+use crate::{remove_diacritics, remove_tones, slugify};
+
+// ==================== Tones ====================
+
+#[test]
+fn testRemoveTonesKeepsShapes() {
+    assert_eq!(
+        remove_tones("Tiếng Việt có dấu, ĐƯỜNG ĂN"),
+        "Tiêng Viêt co dâu, ĐƯƠNG ĂN"
+    );
+}
+
+#[test]
+fn testRemoveTonesFromDecomposedText() {
+    assert_eq!(
+        remove_tones("Vie\u{323}\u{302}t nu\u{31b}\u{301}o\u{31b}c"),
+        "Viêt nươc"
+    );
+}
+
+// ==================== Diacritics ====================
+
+#[test]
+fn testRemoveDiacritics() {
+    assert_eq!(
+        remove_diacritics("Tiếng Việt có dấu, ĐƯỜNG ĂN ở đâu?"),
+        "Tieng Viet co dau, DUONG AN o dau?"
+    );
+}
+
+#[test]
+fn testRemoveDiacriticsFromDecomposedText() {
+    assert_eq!(
+        remove_diacritics("Vie\u{323}\u{302}t nu\u{31b}\u{301}o\u{31b}c"),
+        "Viet nuoc"
+    );
+}
+
+#[test]
+fn testRemoveDiacriticsFromOtherLatinLetters() {
+    assert_eq!(remove_diacritics("Façade über"), "Facade uber");
+}
+
+// ==================== Slugs ====================
+
+#[test]
+fn testSlugify() {
+    assert_eq!(slugify("Tiếng Việt có dấu"), "tieng-viet-co-dau");
+    assert_eq!(slugify("  Đường đi -- khó, (2024)! "), "duong-di-kho-2024");
+    assert_eq!(slugify("!!!"), "");
+}
+
+// ==================== FFI ====================
+
+fn ffi_string(transform: extern "C" fn(*const c_char) -> *mut c_char, input: &str) -> String {
+    let input = CString::new(input).unwrap();
+    let output = transform(input.as_ptr());
+    let text = unsafe { CStr::from_ptr(output) }
+        .to_str()
+        .unwrap()
+        .to_string();
+    vitype_engine_free_string(output);
+    text
+}
+
+#[test]
+fn testFfiStripping() {
+    assert_eq!(ffi_string(vitype_remove_tones, "Tiếng Việt"), "Tiêng Viêt");
+    assert_eq!(
+        ffi_string(vitype_remove_diacritics, "Tiếng Việt"),
+        "Tieng Viet"
+    );
+    assert_eq!(ffi_string(vitype_slugify, "Tiếng Việt"), "tieng-viet");
+}
+
+#[test]
+fn testFfiStrippingRejectsInvalidInput() {
+    assert!(vitype_slugify(std::ptr::null()).is_null());
+    let invalid = [0xFFu8, 0];
+    assert!(vitype_remove_tones(invalid.as_ptr().cast()).is_null());
+}
@@ -0,0 +1,108 @@
+#![allow(non_snake_case)]
+
+use crate::{Syllable, ToneMark};
+
+fn parts(word: &str) -> (String, Option<char>, String, String, Option<ToneMark>) {
+    let syllable = Syllable::parse(word).unwrap_or_else(|| panic!("{word:?} did not parse"));
+    (
+        syllable.onset,
+        syllable.glide,
+        syllable.nucleus,
+        syllable.coda,
+        syllable.tone,
+    )
+}
+
+fn assert_parts(
+    word: &str,
+    onset: &str,
+    glide: Option<char>,
+    nucleus: &str,
+    coda: &str,
+    tone: Option<ToneMark>,
+) {
+    assert_eq!(
+        parts(word),
+        (
+            onset.to_string(),
+            glide,
+            nucleus.to_string(),
+            coda.to_string(),
+            tone
+        ),
+        "{word:?}"
+    );
+}
+
+// ==================== Onsets ====================
+
+#[test]
+fn testParseSimpleSyllables() {
+    assert_parts("a", "", None, "a", "", None);
+    assert_parts("ba", "b", None, "a", "", None);
+    assert_parts("tiếng", "t", None, "iê", "ng", Some(ToneMark::Acute));
+    assert_parts("nghiêng", "ngh", None, "iê", "ng", None);
+    assert_parts("trường", "tr", None, "ươ", "ng", Some(ToneMark::Grave));
+}
+
+#[test]
+fn testParseQuOnset() {
+    assert_parts("quý", "qu", None, "y", "", Some(ToneMark::Acute));
+    assert_parts("qua", "qu", None, "a", "", None);
+    assert_parts("quyền", "qu", None, "yê", "n", Some(ToneMark::Grave));
+}
+
+#[test]
+fn testParseGiOnset() {
+    assert_parts("gì", "g", None, "i", "", Some(ToneMark::Grave));
+    assert_parts("gìn", "g", None, "i", "n", Some(ToneMark::Grave));
+    assert_parts("giá", "gi", None, "a", "", Some(ToneMark::Acute));
+    assert_parts("giường", "gi", None, "ươ", "ng", Some(ToneMark::Grave));
+}
+
+// ==================== Glides and Codas ====================
+
+#[test]
+fn testParseGlides() {
+    assert_parts("hoạch", "h", Some('o'), "a", "ch", Some(ToneMark::DotBelow));
+    assert_parts("khỏe", "kh", Some('o'), "e", "", Some(ToneMark::HookAbove));
+    assert_parts("thuở", "th", Some('u'), "ơ", "", Some(ToneMark::HookAbove));
+    assert_parts("nguyễn", "ng", Some('u'), "yê", "n", Some(ToneMark::Tilde));
+    assert_parts("khuya", "kh", Some('u'), "ya", "", None);
+}
+
+#[test]
+fn testParseSemivowelCodas() {
+    assert_parts("hai", "h", None, "a", "i", None);
+    assert_parts("ngoài", "ng", Some('o'), "a", "i", Some(ToneMark::Grave));
+    assert_parts("người", "ng", None, "ươ", "i", Some(ToneMark::Grave));
+    assert_parts("yêu", "", None, "yê", "u", None);
+    assert_parts("mưu", "m", None, "ư", "u", None);
+}
+
+// ==================== Input Forms ====================
+
+#[test]
+fn testParseKeepsCase() {
+    assert_parts("NGHIÊNG", "NGH", None, "IÊ", "NG", None);
+    assert_parts("Hoàng", "H", Some('o'), "a", "ng", Some(ToneMark::Grave));
+}
+
+#[test]
+fn testParseDecomposedText() {
+    assert_parts(
+        "Vie\u{323}\u{302}t",
+        "V",
+        None,
+        "iê",
+        "t",
+        Some(ToneMark::DotBelow),
+    );
+}
+
+#[test]
+fn testParseRejectsNonSyllables() {
+    for word in ["", "nh", "hello", "áá", "ba ba", "a1", "aeo", "tieng-viet"] {
+        assert_eq!(Syllable::parse(word), None, "{word:?}");
+    }
+}
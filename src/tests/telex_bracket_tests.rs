@@ -0,0 +1,91 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::{action, apply_input, apply_input_with_config};
+use crate::{EngineConfig, InputMethod, VitypeEngine};
+
+fn apply_bracket_input(input: &str) -> String {
+    let config = EngineConfig::builder()
+        .telex_bracket_shortcuts(true)
+        .build();
+    apply_input_with_config(input, config)
+}
+
+// MARK: - Bracket Shortcuts
+
+#[test]
+fn testBracketsTypeHornVowels() {
+    assert_eq!(apply_bracket_input("["), "ơ");
+    assert_eq!(apply_bracket_input("]"), "ư");
+    assert_eq!(apply_bracket_input("{"), "Ơ");
+    assert_eq!(apply_bracket_input("}"), "Ư");
+    assert_eq!(apply_bracket_input("t]"), "tư");
+    assert_eq!(apply_bracket_input("m[i"), "mơi");
+    assert_eq!(apply_bracket_input("ng][i"), "ngươi");
+}
+
+#[test]
+fn testBracketVowelsTakeTones() {
+    assert_eq!(apply_bracket_input("m[if"), "mời");
+    assert_eq!(apply_bracket_input("ng][if"), "người");
+    assert_eq!(apply_bracket_input("t]j"), "tự");
+    assert_eq!(apply_bracket_input("T}S"), "TỨ");
+}
+
+#[test]
+fn testBracketVowelAutoFixesTone() {
+    assert_eq!(apply_bracket_input("t]f[ng"), "tường");
+    assert_eq!(apply_bracket_input("h]s["), "hướ");
+}
+
+#[test]
+fn testBracketEscape() {
+    assert_eq!(apply_bracket_input("[["), "[");
+    assert_eq!(apply_bracket_input("}}"), "}");
+    assert_eq!(apply_bracket_input("t[["), "t[");
+    // After the escape the bracket is punctuation, so the next key starts a new word.
+    assert_eq!(apply_bracket_input("a]]as"), "a]á");
+}
+
+#[test]
+fn testBracketIsPunctuationInForeignWord() {
+    assert_eq!(apply_bracket_input("hello["), "hello[");
+    assert_eq!(apply_bracket_input("ddd["), "dd[");
+}
+
+#[test]
+fn testBracketShortcutsWorkInSimpleTelex() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::SimpleTelex)
+        .telex_bracket_shortcuts(true)
+        .build();
+    assert_eq!(apply_input_with_config("t]", config.clone()), "tư");
+    assert_eq!(apply_input_with_config("tw", config), "tw");
+}
+
+#[test]
+fn testBracketsAreBoundariesByDefault() {
+    assert_eq!(apply_input("t["), "t[");
+    assert_eq!(apply_input("[as]"), "[á]");
+}
+
+#[test]
+fn testBracketsIgnoredOutsideTelex() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .telex_bracket_shortcuts(true)
+        .build();
+    assert_eq!(apply_input_with_config("t[", config), "t[");
+}
+
+#[test]
+fn testBackspaceKeepsBracketVowel() {
+    let config = EngineConfig::builder()
+        .telex_bracket_shortcuts(true)
+        .build();
+    let mut engine = VitypeEngine::with_config(config);
+    for key in ["m", "[", "i"] {
+        engine.process(key);
+    }
+    engine.delete_last_character();
+    assert_eq!(engine.process("f"), Some(action(1, "ờ")));
+}
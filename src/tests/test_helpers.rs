@@ -3,7 +3,7 @@
 //
 // Created by Tran Dat on 24/12/25.
 
-use crate::{InputMethod, KeyTransformAction, TonePlacement, VitypeEngine};
+use crate::{EngineConfig, InputMethod, KeyTransformAction, TonePlacement, VitypeEngine};
 
 pub(super) fn action(delete_count: usize, text: &str) -> KeyTransformAction {
     KeyTransformAction {
@@ -23,7 +23,7 @@ pub(super) fn apply_input(input: &str) -> String {
 /// - Returns: The transformed output string
 pub(super) fn apply_input_with_auto_fix(input: &str, auto_fix_tone: bool) -> String {
     let mut engine = VitypeEngine::new();
-    engine.auto_fix_tone = auto_fix_tone;
+    engine.set_auto_fix_tone(auto_fix_tone);
     let mut output: Vec<char> = Vec::new();
 
     for ch in input.chars() {
@@ -48,7 +48,7 @@ pub(super) fn apply_input_with_free_tone_placement(
     free_tone_placement: bool,
 ) -> String {
     let mut engine = VitypeEngine::new();
-    engine.free_tone_placement = free_tone_placement;
+    engine.set_free_tone_placement(free_tone_placement);
     let mut output: Vec<char> = Vec::new();
 
     for ch in input.chars() {
@@ -74,8 +74,8 @@ pub(super) fn apply_vni_input(input: &str) -> String {
 
 pub(super) fn apply_vni_input_with_auto_fix(input: &str, auto_fix_tone: bool) -> String {
     let mut engine = VitypeEngine::new();
-    engine.input_method = InputMethod::Vni;
-    engine.auto_fix_tone = auto_fix_tone;
+    engine.set_input_method(InputMethod::Vni);
+    engine.set_auto_fix_tone(auto_fix_tone);
 
     let mut output: Vec<char> = Vec::new();
     for ch in input.chars() {
@@ -99,8 +99,8 @@ pub(super) fn apply_vni_input_with_free_tone_placement(
     free_tone_placement: bool,
 ) -> String {
     let mut engine = VitypeEngine::new();
-    engine.input_method = InputMethod::Vni;
-    engine.free_tone_placement = free_tone_placement;
+    engine.set_input_method(InputMethod::Vni);
+    engine.set_free_tone_placement(free_tone_placement);
     let mut output: Vec<char> = Vec::new();
 
     for ch in input.chars() {
@@ -125,7 +125,7 @@ pub(super) fn apply_input_with_tone_placement(
     tone_placement: TonePlacement,
 ) -> String {
     let mut engine = VitypeEngine::new();
-    engine.tone_placement = tone_placement;
+    engine.set_tone_placement(tone_placement);
     let mut output: Vec<char> = Vec::new();
 
     for ch in input.chars() {
@@ -150,8 +150,8 @@ pub(super) fn apply_vni_input_with_tone_placement(
     tone_placement: TonePlacement,
 ) -> String {
     let mut engine = VitypeEngine::new();
-    engine.input_method = InputMethod::Vni;
-    engine.tone_placement = tone_placement;
+    engine.set_input_method(InputMethod::Vni);
+    engine.set_tone_placement(tone_placement);
     let mut output: Vec<char> = Vec::new();
 
     for ch in input.chars() {
@@ -170,3 +170,82 @@ pub(super) fn apply_vni_input_with_tone_placement(
 
     output.into_iter().collect()
 }
+
+pub(super) fn apply_viqr_input(input: &str) -> String {
+    apply_viqr_input_with_auto_fix(input, true)
+}
+
+pub(super) fn apply_viqr_input_with_auto_fix(input: &str, auto_fix_tone: bool) -> String {
+    let mut engine = VitypeEngine::new();
+    engine.set_input_method(InputMethod::Viqr);
+    engine.set_auto_fix_tone(auto_fix_tone);
+
+    let mut output: Vec<char> = Vec::new();
+    for ch in input.chars() {
+        let ch_str = ch.to_string();
+        if let Some(action) = engine.process(&ch_str) {
+            if action.delete_count > 0 && output.len() >= action.delete_count {
+                for _ in 0..action.delete_count {
+                    output.pop();
+                }
+            }
+            output.extend(action.text.chars());
+        } else {
+            output.push(ch);
+        }
+    }
+    output.into_iter().collect()
+}
+
+pub(super) fn apply_simple_telex_input(input: &str) -> String {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::SimpleTelex)
+        .build();
+    apply_input_with_config(input, config)
+}
+
+pub(super) fn apply_input_with_config(input: &str, config: EngineConfig) -> String {
+    let mut engine = VitypeEngine::with_config(config);
+
+    let mut output: Vec<char> = Vec::new();
+    for ch in input.chars() {
+        let ch_str = ch.to_string();
+        if let Some(action) = engine.process(&ch_str) {
+            if action.delete_count > 0 && output.len() >= action.delete_count {
+                for _ in 0..action.delete_count {
+                    output.pop();
+                }
+            }
+            output.extend(action.text.chars());
+        } else {
+            output.push(ch);
+        }
+    }
+    output.into_iter().collect()
+}
+
+/// Types `keys` into `engine` and returns the resulting text. `<` is a backspace.
+pub(super) fn type_keys(engine: &mut VitypeEngine, keys: &str) -> String {
+    let mut output: Vec<char> = Vec::new();
+    for ch in keys.chars() {
+        if ch == '<' {
+            engine.delete_last_character();
+            output.pop();
+            continue;
+        }
+        match engine.process(&ch.to_string()) {
+            Some(action) => {
+                let keep = output.len().saturating_sub(action.delete_count);
+                output.truncate(keep);
+                output.extend(action.text.chars());
+            }
+            None => output.push(ch),
+        }
+    }
+    output.into_iter().collect()
+}
+
+/// A per-process path in the system temp directory, for settings and word-list files.
+pub(super) fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("vitype_core_{}_{}", std::process::id(), name))
+}
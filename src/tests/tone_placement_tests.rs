@@ -21,7 +21,7 @@ mod auto_fix_tone_tests {
     #[test]
     fn testAutoFixToneHoaToHoai() {
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = true;
+        transformer.set_auto_fix_tone(true);
 
         // "hoa" + "f" → "hòa" (tone on o, 2 vowels → 1st)
         let _ = transformer.process("h");
@@ -39,7 +39,7 @@ mod auto_fix_tone_tests {
     #[test]
     fn testAutoFixToneMuaToMuai() {
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = true;
+        transformer.set_auto_fix_tone(true);
 
         // "mua" + "f" → "mùa" (tone on u, 2 vowels → 1st)
         let _ = transformer.process("m");
@@ -56,7 +56,7 @@ mod auto_fix_tone_tests {
     #[test]
     fn testAutoFixToneDisabled() {
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = false;
+        transformer.set_auto_fix_tone(false);
 
         // "hoa" + "f" → "hòa"
         let _ = transformer.process("h");
@@ -72,13 +72,13 @@ mod auto_fix_tone_tests {
     #[test]
     fn testAutoFixToneDefaultEnabled() {
         let transformer = VitypeEngine::new();
-        assert_eq!(transformer.auto_fix_tone, true);
+        assert!(transformer.auto_fix_tone());
     }
 
     #[test]
     fn testAutoFixToneNoTonePresent() {
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = true;
+        transformer.set_auto_fix_tone(true);
 
         // "hoa" + "i" → "hoai" (no tone to reposition)
         let _ = transformer.process("h");
@@ -91,7 +91,7 @@ mod auto_fix_tone_tests {
     #[test]
     fn testAutoFixToneToneAlreadyCorrect() {
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = true;
+        transformer.set_auto_fix_tone(true);
 
         // "ta" + "s" → "tá" (single vowel, tone correct)
         let _ = transformer.process("t");
@@ -106,7 +106,7 @@ mod auto_fix_tone_tests {
     #[test]
     fn testAutoFixToneWithNucleusVowel() {
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = true;
+        transformer.set_auto_fix_tone(true);
 
         // "tuo" + "o" → "tuô" (circumflex transform)
         let _ = transformer.process("t");
@@ -232,7 +232,7 @@ mod auto_fix_tone_tests {
         // Previously, lastTransformKey wasn't cleared when auto-fix returned an action,
         // which could cause incorrect escape sequence detection.
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = true;
+        transformer.set_auto_fix_tone(true);
 
         // "hoa" + "f" → "hòa" (lastTransformKey = f)
         let _ = transformer.process("h");
@@ -606,7 +606,7 @@ mod gi_tone_placement_tests {
     fn testAutoFixToneGIToGIA() {
         // Type "gis" → "gí", then add "a" → should become "giá"
         let mut transformer = VitypeEngine::new();
-        transformer.auto_fix_tone = true;
+        transformer.set_auto_fix_tone(true);
 
         let _ = transformer.process("g");
         let _ = transformer.process("i");
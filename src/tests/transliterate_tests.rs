@@ -0,0 +1,86 @@
+#![allow(non_snake_case)]
+
+use super::test_helpers::apply_input;
+use crate::{transliterate, EngineConfig, InputMethod, TonePlacement};
+
+fn telex(text: &str) -> String {
+    transliterate(text, InputMethod::Telex, EngineConfig::default())
+}
+
+#[test]
+fn testTransliterateTelexSentence() {
+    assert_eq!(
+        telex("Tooi yeeu tieengs Vieetj Nam."),
+        "Tôi yêu tiếng Việt Nam."
+    );
+}
+
+#[test]
+fn testTransliterateKeepsWhitespaceAndPunctuation() {
+    assert_eq!(
+        telex("Xin chaof,\n\tbanj khoer khoong?\r\n(ddi ddaau!)"),
+        "Xin chào,\n\tbạn khỏe không?\r\n(đi đâu!)"
+    );
+}
+
+#[test]
+fn testTransliterateStartsEachWordFresh() {
+    // Tone keys after a boundary start a new word instead of marking the previous one.
+    assert_eq!(telex("ba s"), "ba s");
+    assert_eq!(telex("as.as"), "á.á");
+}
+
+#[test]
+fn testTransliterateMatchesKeyByKeyTyping() {
+    let input = "Nguwowif Vieetj thuwowngf vieets chuwx quoocs nguwx.";
+    assert_eq!(telex(input), apply_input(input));
+}
+
+#[test]
+fn testTransliterateForeignWords() {
+    let config = EngineConfig::builder()
+        .literal_foreign_initials(true)
+        .build();
+    assert_eq!(
+        transliterate("zoom vaf facebook", InputMethod::Telex, config),
+        "zoom và facebook"
+    );
+}
+
+#[test]
+fn testTransliterateVniAndViqr() {
+    assert_eq!(
+        transliterate("Ngu7o72i Vie65t", InputMethod::Vni, EngineConfig::default()),
+        "Người Việt"
+    );
+    assert_eq!(
+        transliterate(
+            "Ngu+o+`i Vie^.t",
+            InputMethod::Viqr,
+            EngineConfig::default()
+        ),
+        "Người Việt"
+    );
+}
+
+#[test]
+fn testTransliterateInputMethodOverridesConfig() {
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .build();
+    assert_eq!(transliterate("vieejt", InputMethod::Telex, config), "việt");
+}
+
+#[test]
+fn testTransliterateUsesConfigOptions() {
+    let config = EngineConfig::builder()
+        .tone_placement(TonePlacement::NucleusOnly)
+        .build();
+    assert_eq!(transliterate("hoaf", InputMethod::Telex, config), "hoà");
+    assert_eq!(telex("hoaf"), "hòa");
+}
+
+#[test]
+fn testTransliterateEmptyText() {
+    assert_eq!(telex(""), "");
+}
@@ -0,0 +1,72 @@
+#![allow(non_snake_case)]
+
+use super::{apply_viqr_input, create_viqr_engine};
+
+#[test]
+fn testWhitespaceEndsWord() {
+    assert_eq!(apply_viqr_input("a' a`"), "á à");
+    assert_eq!(apply_viqr_input("a'\na`"), "á\nà");
+}
+
+#[test]
+fn testModifierAtWordStartIsLiteral() {
+    assert_eq!(apply_viqr_input("'a"), "'a");
+    assert_eq!(apply_viqr_input("(a)"), "(a)");
+    assert_eq!(apply_viqr_input("+1"), "+1");
+}
+
+#[test]
+fn testUnusedModifierEndsWord() {
+    // No vowel to take the tone: the key is punctuation and the next word starts fresh.
+    assert_eq!(apply_viqr_input("b'a'"), "b'á");
+    assert_eq!(apply_viqr_input("ab-cd"), "ab-cd");
+    assert_eq!(apply_viqr_input("x?"), "x?");
+}
+
+#[test]
+fn testModifierIsPunctuationInForeignWord() {
+    assert_eq!(apply_viqr_input("hello."), "hello.");
+    assert_eq!(apply_viqr_input("hello? yes"), "hello? yes");
+}
+
+#[test]
+fn testNonModifierPunctuationEndsWord() {
+    assert_eq!(apply_viqr_input("ba,ba'"), "ba,bá");
+    assert_eq!(apply_viqr_input("ba!"), "ba!");
+}
+
+#[test]
+fn testDigitsEndWord() {
+    assert_eq!(apply_viqr_input("a1'"), "a1'");
+}
+
+// MARK: - Backslash Escape
+
+#[test]
+fn testBackslashMakesModifierLiteral() {
+    assert_eq!(apply_viqr_input("ba\\."), "ba.");
+    assert_eq!(apply_viqr_input("ba\\. Ca'"), "ba. Cá");
+}
+
+#[test]
+fn testBackslashMakesLetterLiteral() {
+    assert_eq!(apply_viqr_input("d\\d"), "dd");
+    assert_eq!(apply_viqr_input("d\\da"), "dda");
+}
+
+#[test]
+fn testDoubleBackslashIsLiteralBackslash() {
+    assert_eq!(apply_viqr_input("\\\\"), "\\");
+    assert_eq!(apply_viqr_input("a\\\\'"), "a\\'");
+}
+
+#[test]
+fn testBackslashIsConsumedWithoutOutput() {
+    let mut transformer = create_viqr_engine();
+
+    assert_eq!(transformer.process("a"), None);
+    let action = transformer.process("\\").unwrap();
+    assert_eq!(action.delete_count, 0);
+    assert_eq!(action.text, "");
+    assert_eq!(transformer.process("'"), None);
+}
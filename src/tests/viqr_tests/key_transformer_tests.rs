@@ -0,0 +1,183 @@
+#![allow(non_snake_case)]
+
+use super::{action, apply_viqr_input, create_viqr_engine};
+use crate::VitypeEngine;
+
+fn apply_key(engine: &mut VitypeEngine, output: &mut Vec<char>, ch: char) {
+    let ch_str = ch.to_string();
+    if let Some(action) = engine.process(&ch_str) {
+        if action.delete_count > 0 && output.len() >= action.delete_count {
+            for _ in 0..action.delete_count {
+                output.pop();
+            }
+        }
+        output.extend(action.text.chars());
+    } else {
+        output.push(ch);
+    }
+}
+
+fn backspace(engine: &mut VitypeEngine, output: &mut Vec<char>) {
+    engine.delete_last_character();
+    output.pop();
+}
+
+// MARK: - Consonant Tests (dd / d- -> đ)
+
+#[test]
+fn testConsonantDD() {
+    let mut transformer = create_viqr_engine();
+
+    assert_eq!(transformer.process("d"), None);
+    assert_eq!(transformer.process("d"), Some(action(1, "đ")));
+}
+
+#[test]
+fn testConsonantDDash() {
+    let mut transformer = create_viqr_engine();
+
+    assert_eq!(transformer.process("d"), None);
+    assert_eq!(transformer.process("-"), Some(action(1, "đ")));
+}
+
+#[test]
+fn testConsonantDUppercase() {
+    assert_eq!(apply_viqr_input("DDi"), "Đi");
+    assert_eq!(apply_viqr_input("D-i"), "Đi");
+}
+
+#[test]
+fn testEscapeDStroke() {
+    assert_eq!(apply_viqr_input("ddd"), "dd");
+    assert_eq!(apply_viqr_input("d--"), "d-");
+}
+
+// MARK: - Vowel Shape Tests
+
+#[test]
+fn testCircumflex() {
+    assert_eq!(apply_viqr_input("a^"), "â");
+    assert_eq!(apply_viqr_input("e^"), "ê");
+    assert_eq!(apply_viqr_input("o^"), "ô");
+    assert_eq!(apply_viqr_input("A^"), "Â");
+}
+
+#[test]
+fn testHorn() {
+    assert_eq!(apply_viqr_input("o+"), "ơ");
+    assert_eq!(apply_viqr_input("u+"), "ư");
+    assert_eq!(apply_viqr_input("U+"), "Ư");
+}
+
+#[test]
+fn testBreve() {
+    assert_eq!(apply_viqr_input("a("), "ă");
+    assert_eq!(apply_viqr_input("A("), "Ă");
+}
+
+#[test]
+fn testCircumflexOverridesBreve() {
+    assert_eq!(apply_viqr_input("a(^"), "â");
+    assert_eq!(apply_viqr_input("o+^"), "ô");
+}
+
+#[test]
+fn testCompoundHornUo() {
+    assert_eq!(apply_viqr_input("uo+"), "ươ");
+    assert_eq!(apply_viqr_input("u+o+"), "ươ");
+    assert_eq!(apply_viqr_input("nuoc+"), "nươc");
+}
+
+#[test]
+fn testFreeTransformAfterFinalConsonant() {
+    assert_eq!(apply_viqr_input("viet^"), "viêt");
+}
+
+#[test]
+fn testEscapeShape() {
+    assert_eq!(apply_viqr_input("a^^"), "a^");
+    assert_eq!(apply_viqr_input("a(("), "a(");
+    assert_eq!(apply_viqr_input("u++"), "u+");
+}
+
+// MARK: - Tone Tests
+
+#[test]
+fn testTones() {
+    assert_eq!(apply_viqr_input("a'"), "á");
+    assert_eq!(apply_viqr_input("a`"), "à");
+    assert_eq!(apply_viqr_input("a?"), "ả");
+    assert_eq!(apply_viqr_input("a~"), "ã");
+    assert_eq!(apply_viqr_input("a."), "ạ");
+}
+
+#[test]
+fn testToneOnShapedVowel() {
+    assert_eq!(apply_viqr_input("a^'"), "ấ");
+    assert_eq!(apply_viqr_input("e^."), "ệ");
+    assert_eq!(apply_viqr_input("o+~"), "ỡ");
+    assert_eq!(apply_viqr_input("a(."), "ặ");
+    assert_eq!(apply_viqr_input("O+'"), "Ớ");
+}
+
+#[test]
+fn testShapeAfterTone() {
+    assert_eq!(apply_viqr_input("a'^"), "ấ");
+    assert_eq!(apply_viqr_input("o`+"), "ờ");
+}
+
+#[test]
+fn testToneReplacement() {
+    assert_eq!(apply_viqr_input("a'`"), "à");
+}
+
+#[test]
+fn testEscapeTone() {
+    let mut transformer = create_viqr_engine();
+
+    assert_eq!(transformer.process("a"), None);
+    assert_eq!(transformer.process("'"), Some(action(1, "á")));
+    assert_eq!(transformer.process("'"), Some(action(1, "a'")));
+}
+
+// MARK: - Words
+
+#[test]
+fn testWords() {
+    assert_eq!(apply_viqr_input("Vie^.t Nam"), "Việt Nam");
+    assert_eq!(apply_viqr_input("tie^'ng"), "tiếng");
+    assert_eq!(apply_viqr_input("ngu+o+`i"), "người");
+    assert_eq!(apply_viqr_input("d-u+o+.c"), "được");
+    assert_eq!(apply_viqr_input("quo^'c"), "quốc");
+}
+
+#[test]
+fn testToneKeyTypedAfterFinalConsonant() {
+    assert_eq!(apply_viqr_input("vie^t."), "việt");
+    assert_eq!(apply_viqr_input("muo^n'"), "muốn");
+}
+
+#[test]
+fn testSentence() {
+    assert_eq!(
+        apply_viqr_input("Ba.n co' kho?e kho^ng\\?"),
+        "Bạn có khỏe không?"
+    );
+}
+
+// MARK: - Backspace
+
+#[test]
+fn testBackspaceAcrossWordBoundaryCanEditPreviousTone() {
+    let mut engine = create_viqr_engine();
+    let mut output: Vec<char> = Vec::new();
+
+    for ch in "ma' ".chars() {
+        apply_key(&mut engine, &mut output, ch);
+    }
+    assert_eq!(output.iter().collect::<String>(), "má ");
+
+    backspace(&mut engine, &mut output);
+    apply_key(&mut engine, &mut output, '`');
+    assert_eq!(output.iter().collect::<String>(), "mà");
+}
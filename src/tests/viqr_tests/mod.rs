@@ -0,0 +1,14 @@
+#![allow(non_snake_case)]
+
+mod boundary_tests;
+mod key_transformer_tests;
+mod tone_placement_tests;
+
+pub(super) use super::test_helpers::{action, apply_viqr_input, apply_viqr_input_with_auto_fix};
+use crate::{InputMethod, VitypeEngine};
+
+fn create_viqr_engine() -> VitypeEngine {
+    let mut engine = VitypeEngine::new();
+    engine.set_input_method(InputMethod::Viqr);
+    engine
+}
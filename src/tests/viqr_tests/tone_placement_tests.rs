@@ -0,0 +1,55 @@
+#![allow(non_snake_case)]
+
+use super::{action, apply_viqr_input, apply_viqr_input_with_auto_fix, create_viqr_engine};
+
+#[test]
+fn testToneOnTwoVowelsFirst() {
+    assert_eq!(apply_viqr_input("hoa`"), "hòa");
+    assert_eq!(apply_viqr_input("mu+a'"), "mứa");
+}
+
+#[test]
+fn testToneOnTwoVowelsWithFinalConsonant() {
+    assert_eq!(apply_viqr_input("toa'n"), "toán");
+    assert_eq!(apply_viqr_input("toan'"), "toán");
+}
+
+#[test]
+fn testToneOnNucleusOnlyVowel() {
+    assert_eq!(apply_viqr_input("thu+o+ng?"), "thưởng");
+    assert_eq!(apply_viqr_input("tuo^i?"), "tuổi");
+}
+
+#[test]
+fn testToneOnThreeVowelsMiddle() {
+    assert_eq!(apply_viqr_input("khuya?"), "khuỷa");
+}
+
+#[test]
+fn testQuAndGiClusters() {
+    assert_eq!(apply_viqr_input("qua'"), "quá");
+    assert_eq!(apply_viqr_input("gia'"), "giá");
+    assert_eq!(apply_viqr_input("gi`"), "gì");
+}
+
+#[test]
+fn testAutoFixToneHoaToHoai() {
+    let mut transformer = create_viqr_engine();
+
+    let _ = transformer.process("h");
+    let _ = transformer.process("o");
+    let _ = transformer.process("a");
+    assert_eq!(transformer.process("`"), Some(action(2, "òa")));
+    assert_eq!(transformer.process("i"), Some(action(2, "oài")));
+}
+
+#[test]
+fn testAutoFixToneDisabled() {
+    assert_eq!(apply_viqr_input_with_auto_fix("hoa`i", true), "hoài");
+    assert_eq!(apply_viqr_input_with_auto_fix("hoa`i", false), "hòai");
+}
+
+#[test]
+fn testAutoFixToneAfterShape() {
+    assert_eq!(apply_viqr_input("uo'^"), "uố");
+}
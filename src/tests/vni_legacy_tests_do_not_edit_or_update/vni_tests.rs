@@ -1,6 +1,6 @@
 // VNI input method tests
 
-use crate::{InputMethod, KeyTransformAction, VitypeEngine};
+use crate::{EngineConfig, InputMethod, KeyTransformAction, VitypeEngine};
 
 fn action(delete_count: usize, text: &str) -> KeyTransformAction {
     KeyTransformAction {
@@ -11,7 +11,11 @@ fn action(delete_count: usize, text: &str) -> KeyTransformAction {
 
 fn create_vni_engine() -> VitypeEngine {
     let mut engine = VitypeEngine::new();
-    engine.input_method = InputMethod::Vni;
+    engine.apply_config(
+        EngineConfig::builder()
+            .input_method(InputMethod::Vni)
+            .build(),
+    );
     engine
 }
 
@@ -21,7 +25,11 @@ fn apply_vni_input(input: &str) -> String {
 
 fn apply_vni_input_with_auto_fix(input: &str, auto_fix_tone: bool) -> String {
     let mut engine = create_vni_engine();
-    engine.auto_fix_tone = auto_fix_tone;
+    let config = EngineConfig::builder()
+        .input_method(InputMethod::Vni)
+        .auto_fix_tone(auto_fix_tone)
+        .build();
+    engine.apply_config(config);
     let mut output: Vec<char> = Vec::new();
 
     for ch in input.chars() {
@@ -1,6 +1,9 @@
 #![allow(non_snake_case)]
 
+// These files wrap their tests in a module of the same name.
+#[allow(clippy::module_inception)]
 mod foreign_consonant_tests;
+#[allow(clippy::module_inception)]
 mod key_transformer_tests;
 mod tone_placement_tests;
 mod w_transform_tests;
@@ -12,6 +15,6 @@ use crate::{InputMethod, VitypeEngine};
 
 fn create_vni_engine() -> VitypeEngine {
     let mut engine = VitypeEngine::new();
-    engine.input_method = InputMethod::Vni;
+    engine.set_input_method(InputMethod::Vni);
     engine
 }
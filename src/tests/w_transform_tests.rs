@@ -800,3 +800,40 @@ mod compound_uow_transform_tests {
         assert_eq!(r6, "hưỡu");
     }
 }
+
+// MARK: - Simple Telex Tests
+// Simple Telex reuses the Telex rules, except that w never stands alone as ư.
+mod simple_telex_w_transform_tests {
+    use super::super::test_helpers::{apply_input, apply_simple_telex_input};
+
+    #[test]
+    fn testWAfterVowelMatchesTelex() {
+        for input in [
+            "hoiw", "moiwf", "nguoiw", "huouw", "houwu", "uaw", "uwa", "muaw", "uow", "nuocw",
+            "aw", "ow", "uw", "trowif", "Uaw",
+        ] {
+            assert_eq!(
+                apply_simple_telex_input(input),
+                apply_input(input),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn testStandaloneWStaysLiteral() {
+        assert_eq!(apply_simple_telex_input("w"), "w");
+        assert_eq!(apply_simple_telex_input("W"), "W");
+        assert_eq!(apply_simple_telex_input("tw"), "tw");
+        assert_eq!(apply_simple_telex_input("ww"), "ww");
+        assert_eq!(apply_simple_telex_input("ws"), "ws");
+        assert_eq!(apply_simple_telex_input("w tw"), "w tw");
+    }
+
+    #[test]
+    fn testWEscapeAfterVowel() {
+        assert_eq!(apply_simple_telex_input("aww"), "aw");
+        assert_eq!(apply_simple_telex_input("uww"), "uw");
+    }
+}
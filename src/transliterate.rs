@@ -0,0 +1,37 @@
+//! Whole-string transliteration: typed text in, Vietnamese text out.
+
+use crate::common::InputMethod;
+use crate::config::EngineConfig;
+use crate::VitypeEngine;
+
+/// Runs `text` through a fresh engine one key at a time, as if it were typed with
+/// `input_method`, and returns the resulting text.
+///
+/// Spaces, punctuation and newlines pass through and end words exactly as they would when
+/// typing, so foreign words, escapes and the other options in `config` behave the same way.
+/// `input_method` overrides `config.input_method`. The result is always precomposed Unicode;
+/// pass it to [`crate::encode_text`] for another encoding.
+///
+/// ```
+/// use vitype_core::{transliterate, EngineConfig, InputMethod};
+///
+/// let text = transliterate("Tieengs Vieetj, okay?", InputMethod::Telex, EngineConfig::default());
+/// assert_eq!(text, "Tiếng Việt, okay?");
+/// ```
+pub fn transliterate(text: &str, input_method: InputMethod, config: EngineConfig) -> String {
+    let mut engine = VitypeEngine::with_config(config);
+    engine.set_input_method(input_method);
+
+    let mut output: Vec<char> = Vec::with_capacity(text.len());
+    let mut key = [0u8; 4];
+    for ch in text.chars() {
+        match engine.process(ch.encode_utf8(&mut key)) {
+            Some(action) => {
+                output.truncate(output.len().saturating_sub(action.delete_count));
+                output.extend(action.text.chars());
+            }
+            None => output.push(ch),
+        }
+    }
+    output.into_iter().collect()
+}
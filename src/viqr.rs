@@ -0,0 +1,26 @@
+use once_cell::sync::Lazy;
+
+use crate::keymap::{KeyAction, Keymap, ToneMark};
+
+// ==================== VIQR Keymap ====================
+
+pub(super) const VIQR_LITERAL_PREFIX: char = '\\';
+
+/// Punctuation marks tones and shapes inside a word and ends it elsewhere; `dd` and `d-`
+/// type đ and `\` makes the next key literal.
+pub(super) static VIQR_KEYMAP: Lazy<Keymap> = Lazy::new(|| {
+    Keymap::builder("VIQR")
+        .key('\'', KeyAction::Tone(ToneMark::Acute))
+        .key('`', KeyAction::Tone(ToneMark::Grave))
+        .key('?', KeyAction::Tone(ToneMark::HookAbove))
+        .key('~', KeyAction::Tone(ToneMark::Tilde))
+        .key('.', KeyAction::Tone(ToneMark::DotBelow))
+        .key('^', KeyAction::Circumflex)
+        .key('+', KeyAction::Horn)
+        .key('(', KeyAction::Breve)
+        .key('d', KeyAction::DStroke)
+        .key('D', KeyAction::DStroke)
+        .key('-', KeyAction::DStroke)
+        .literal_prefix(Some(VIQR_LITERAL_PREFIX))
+        .build()
+});